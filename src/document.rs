@@ -6,15 +6,37 @@ use html5ever::tree_builder;
 use html5ever::tree_builder::{ElementFlags, NodeOrText, QuirksMode, TreeSink};
 use html5ever::ParseOpts;
 use html5ever::{local_name, namespace_url, ns};
-use html5ever::{Attribute, QualName};
+use html5ever::{Attribute, LocalName, QualName};
 
 use tendril::{StrTendril, TendrilSink};
 
-use crate::dom_tree::Tree;
+use crate::dom_tree::{Tree, TreeNodeOps};
 use crate::entities::wrap_tendril;
 use crate::matcher::{MatchScope, Matcher, Matches};
-use crate::node::{Element, NodeData, NodeId, NodeRef, TreeNode};
+use crate::node::{
+    collapse_whitespace, descendant_nodes, is_preformatted, normalized_char_count, Element,
+    NodeData, NodeId, NodeRef, TreeNode,
+};
 use crate::selection::Selection;
+
+/// Summary counters of a document, returned by [`Document::stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DocumentStats {
+    /// The number of elements.
+    pub elements: usize,
+    /// The number of text nodes.
+    pub text_nodes: usize,
+    /// The number of comments.
+    pub comments: usize,
+    /// The number of links: `a` elements with the `href` attribute.
+    pub links: usize,
+    /// The number of `img` elements.
+    pub images: usize,
+    /// The number of text characters, where each run of whitespace counts as a single character.
+    /// The contents of `script`, `style` and `noscript` elements are not counted.
+    pub text_length: usize,
+}
+
 /// Document represents an HTML document to be manipulated.
 #[derive(Clone)]
 pub struct Document {
@@ -51,10 +73,52 @@ impl<T: Into<StrTendril>> From<T> for Document {
     }
 }
 
+impl Document {
+    /// Creates a new document from bytes, which are expected to be UTF-8 encoded HTML.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD REPLACEMENT CHARACTER` before parsing,
+    /// like in [`String::from_utf8_lossy`], so a malformed byte doesn't prevent parsing the rest of the document.
+    /// It doesn't detect the encoding of the document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from_str_lossy(b"<p>Caf\xE9</p><p>ok</p>");
+    /// assert_eq!(doc.select("p").first().text().as_ref(), "Caf\u{FFFD}");
+    /// assert_eq!(doc.select("p").length(), 2);
+    /// ```
+    pub fn from_str_lossy(html: &[u8]) -> Self {
+        Document::from(String::from_utf8_lossy(html).as_ref())
+    }
+}
+
 // fragment
 impl Document {
     /// Create a new html document fragment
     pub fn fragment<T: Into<StrTendril>>(html: T) -> Self {
+        Document::fragment_with_context(html, "body")
+    }
+
+    /// Create a new html document fragment, parsed as if it was the contents of the `context` element.
+    ///
+    /// Some elements can be parsed only inside a specific parent, e.g. `<tr>` and `<td>`
+    /// are dropped when the fragment is parsed in the default `body` context.
+    /// The `context` element name is case-insensitive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let fragment = Document::fragment_with_context("<tr><td>x</td></tr>", "tbody");
+    /// assert!(fragment.select("tr > td").exists());
+    ///
+    /// let fragment = Document::fragment("<tr><td>x</td></tr>");
+    /// assert!(!fragment.select("td").exists());
+    /// ```
+    pub fn fragment_with_context<T: Into<StrTendril>>(html: T, context: &str) -> Self {
         html5ever::parse_fragment(
             Document::fragment_sink(),
             ParseOpts {
@@ -65,11 +129,89 @@ impl Document {
                     ..Default::default()
                 },
             },
-            QualName::new(None, ns!(html), local_name!("body")),
+            QualName::new(
+                None,
+                ns!(html),
+                LocalName::from(context.to_ascii_lowercase()),
+            ),
             Vec::new(),
         )
         .one(html)
     }
+
+    /// Creates a new html document fragment from copies of the given nodes.
+    ///
+    /// The nodes may belong to different documents, they are copied with their descendants in the given order.
+    /// The resulting fragment has the same structure as one created with [`Document::fragment`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<h1>Title</h1><p>Intro</p><p>Body</p>");
+    /// let sel = doc.select("h1, p:last-child");
+    /// let fragment = Document::fragment_from_nodes(sel.nodes());
+    /// assert_eq!(fragment.select("html").inner_html().as_ref(), "<h1>Title</h1><p>Body</p>");
+    /// ```
+    pub fn fragment_from_nodes(nodes: &[NodeRef]) -> Self {
+        let fragment = Document::fragment_sink();
+        let html_id = fragment.tree.create_node(NodeData::Element(Element::new(
+            QualName::new(None, ns!(html), local_name!("html")),
+            Vec::new(),
+            None,
+            false,
+        )));
+        fragment
+            .tree
+            .append_child_of(&fragment.tree.root_id(), &html_id);
+        fragment.tree.copy_nodes_with_fn(nodes, |new_node_id| {
+            fragment.tree.append_child_of(&html_id, &new_node_id);
+        });
+        fragment
+    }
+
+    /// Parses the html fragment into the document's tree and returns its top-level nodes.
+    ///
+    /// The returned nodes are detached: they belong to the document's tree, but have no parent,
+    /// so they can be inserted anywhere in the document, e.g. with [`NodeRef::append_child`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"></div>"#);
+    /// let nodes = doc.parse_fragment_nodes("<p>One</p><p>Two</p>");
+    /// assert_eq!(nodes.len(), 2);
+    ///
+    /// let main_sel = doc.select("#main");
+    /// let main = main_sel.nodes().first().unwrap();
+    /// for node in nodes.iter().rev() {
+    ///     main.prepend_child(node);
+    /// }
+    /// assert_eq!(main.inner_html().as_ref(), "<p>One</p><p>Two</p>");
+    /// ```
+    pub fn parse_fragment_nodes<T: Into<StrTendril>>(&self, html: T) -> Vec<NodeRef<'_>> {
+        let fragment = Document::fragment(html);
+        let mut nodes = self.tree.nodes.borrow_mut();
+        let mut top_ids = vec![];
+        TreeNodeOps::merge_with_fn(&mut nodes, fragment.tree, |tree_nodes, new_node_id| {
+            let mut next_node_id = Some(new_node_id);
+            while let Some(node) = next_node_id.and_then(|id| tree_nodes.get(id.value)) {
+                top_ids.push(node.id);
+                next_node_id = node.next_sibling;
+            }
+            for node_id in top_ids.iter() {
+                TreeNodeOps::remove_from_parent(tree_nodes, node_id);
+            }
+        });
+        top_ids
+            .into_iter()
+            .map(|id| NodeRef::new(id, &self.tree))
+            .collect()
+    }
+
     /// Create a new sink for a html document fragment
     pub fn fragment_sink() -> Self {
         Self {
@@ -153,6 +295,405 @@ impl Document {
     pub fn normalize(&self) {
         self.root().normalize();
     }
+
+    /// Removes elements with the given names, which are empty (see [`NodeRef::is_empty_element`]).
+    ///
+    /// Removing an element may make its parent empty, so the removal repeats until there are no empty elements left.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"><div><p> </p><span></span></div><p>Text</p></div>"#);
+    /// doc.remove_empty_elements(&["div", "p", "span"]);
+    /// assert_eq!(doc.select("#main").inner_html().as_ref(), "<p>Text</p>");
+    /// ```
+    pub fn remove_empty_elements(&self, tags: &[&str]) {
+        loop {
+            let empty_nodes: Vec<NodeRef> = self
+                .root()
+                .descendants_it()
+                .filter(|node| {
+                    node.node_name()
+                        .map_or(false, |name| tags.contains(&name.as_ref()))
+                        && node.is_empty_element()
+                })
+                .collect();
+            if empty_nodes.is_empty() {
+                break;
+            }
+            for node in empty_nodes {
+                node.remove_from_parent();
+            }
+        }
+    }
+
+    /// Removes all elements matching the CSS selector, together with their descendants.
+    ///
+    /// Returns the number of matched elements, including matched elements nested in other matched ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<nav>Menu</nav><p>Text</p><div class="ad">Ad</div>"#);
+    /// assert_eq!(doc.prune("nav, .ad"), 2);
+    /// assert_eq!(doc.select("body").inner_html().as_ref(), "<p>Text</p>");
+    /// ```
+    pub fn prune(&self, sel: &str) -> usize {
+        let ids: Vec<NodeId> = self
+            .select(sel)
+            .nodes()
+            .iter()
+            .map(|node| node.id)
+            .collect();
+        let mut nodes = self.tree.nodes.borrow_mut();
+        for id in ids.iter() {
+            TreeNodeOps::remove_from_parent(&mut nodes, id);
+        }
+        ids.len()
+    }
+
+    /// Sets the DOCTYPE of the document, replacing the existing one, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<html><head></head><body></body></html>");
+    /// doc.set_doctype("html", "", "");
+    /// assert_eq!(doc.html().as_ref(), "<!DOCTYPE html><html><head></head><body></body></html>");
+    /// ```
+    pub fn set_doctype(&self, name: &str, public_id: &str, system_id: &str) {
+        let root = self.root();
+        let doctype = self.tree.new_doctype(name, public_id, system_id);
+        let old_doctype = root.children_it(false).find(|node| node.is_doctype());
+        match old_doctype {
+            Some(old_doctype) => old_doctype.replace_with(&doctype),
+            None => root.prepend_child(&doctype),
+        }
+    }
+
+    /// Returns the outline of the document: a heading level, a trimmed text and a node id
+    /// of every heading element (`h1`-`h6`) in the document order.
+    ///
+    /// It is useful for generating a table of contents.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<h1>Title</h1><p>Intro</p><h2>Section</h2>");
+    /// let outline: Vec<(u8, String)> = doc
+    ///     .outline()
+    ///     .into_iter()
+    ///     .map(|(level, text, _)| (level, text.to_string()))
+    ///     .collect();
+    /// assert_eq!(outline, vec![(1, "Title".to_string()), (2, "Section".to_string())]);
+    /// ```
+    pub fn outline(&self) -> Vec<(u8, StrTendril, NodeId)> {
+        let root_id = self.tree.root_id();
+        let nodes = self.tree.nodes.borrow();
+        descendant_nodes(Ref::clone(&nodes), &root_id)
+            .filter_map(|id| {
+                let el = nodes.get(id.value)?.as_element()?;
+                let level = match el.name.local {
+                    local_name!("h1") => 1,
+                    local_name!("h2") => 2,
+                    local_name!("h3") => 3,
+                    local_name!("h4") => 4,
+                    local_name!("h5") => 5,
+                    local_name!("h6") => 6,
+                    _ => return None,
+                };
+                let text = TreeNodeOps::text_of(Ref::clone(&nodes), id);
+                Some((level, StrTendril::from(text.trim()), id))
+            })
+            .collect()
+    }
+
+    /// Collects summary counters of the document: the number of elements, text nodes, comments,
+    /// links and images, and the length of the text, in a single traversal.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p>Hello,   <a href="/">World</a><img src="a.png"></p><!-- note -->"#);
+    /// let stats = doc.stats();
+    /// assert_eq!(stats.links, 1);
+    /// assert_eq!(stats.images, 1);
+    /// assert_eq!(stats.comments, 1);
+    /// assert_eq!(stats.text_length, "Hello, World".len());
+    /// ```
+    pub fn stats(&self) -> DocumentStats {
+        let root_id = self.tree.root_id();
+        let nodes = self.tree.nodes.borrow();
+        let mut stats = DocumentStats::default();
+        for id in descendant_nodes(Ref::clone(&nodes), &root_id) {
+            let Some(node) = nodes.get(id.value) else {
+                continue;
+            };
+            match node.data {
+                NodeData::Element(ref el) => {
+                    stats.elements += 1;
+                    match el.name.local {
+                        local_name!("a") if el.attr("href").is_some() => stats.links += 1,
+                        local_name!("img") => stats.images += 1,
+                        _ => {}
+                    }
+                }
+                NodeData::Text { ref contents } => {
+                    stats.text_nodes += 1;
+                    let in_raw_text = node
+                        .parent
+                        .and_then(|parent_id| nodes.get(parent_id.value))
+                        .and_then(|parent| parent.as_element())
+                        .map_or(false, |el| {
+                            matches!(
+                                el.name.local,
+                                local_name!("script")
+                                    | local_name!("style")
+                                    | local_name!("noscript")
+                            )
+                        });
+                    if !in_raw_text {
+                        stats.text_length += normalized_char_count(contents);
+                    }
+                }
+                NodeData::Comment { .. } => stats.comments += 1,
+                _ => {}
+            }
+        }
+        stats
+    }
+
+    /// Returns an iterator over all text nodes of the document in document order.
+    ///
+    /// The iterator keeps the document tree borrowed until it is dropped,
+    /// so the tree must not be modified while iterating, otherwise it panics.
+    /// To modify text nodes, collect them first, e.g. with [`Document::text_nodes`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<p>Hello <b>World</b></p>");
+    /// let texts: Vec<String> = doc.text_nodes_it().map(|n| n.text().to_string()).collect();
+    /// assert_eq!(texts, vec!["Hello ", "World"]);
+    /// ```
+    pub fn text_nodes_it(&self) -> impl Iterator<Item = NodeRef<'_>> {
+        let root_id = self.tree.root_id();
+        let nodes = self.tree.nodes.borrow();
+        let text_nodes = Ref::clone(&nodes);
+        descendant_nodes(nodes, &root_id)
+            .filter(move |id| text_nodes.get(id.value).map_or(false, |n| n.is_text()))
+            .map(move |id| NodeRef::new(id, &self.tree))
+    }
+
+    /// Returns all text nodes of the document in document order.
+    ///
+    /// Unlike [`Document::text_nodes_it`], it releases the tree borrow,
+    /// so the returned nodes can be modified.
+    pub fn text_nodes(&self) -> Vec<NodeRef<'_>> {
+        self.text_nodes_it().collect()
+    }
+
+    /// Calls the closure with a single mutable borrow of the document's node arena.
+    ///
+    /// This is an escape hatch for bulk edits: inside the closure the nodes can be modified
+    /// directly or with [`TreeNodeOps`] functions, without borrowing the tree again for every change,
+    /// which would otherwise panic with `BorrowMutError` if a borrow is still held.
+    /// The closure must not access the document through other handles (e.g. [`NodeRef`]),
+    /// and it is responsible for keeping the links between nodes (parent, children, siblings) consistent.
+    /// The nodes are passed as a slice, so they can't be added, removed or reordered,
+    /// which would invalidate existing [`NodeId`]s and [`NodeRef`]s.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the links between nodes are inconsistent after the closure returns.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<p><b>a</b><b>b</b></p>");
+    /// doc.with_nodes_mut(|nodes| {
+    ///     for node in nodes.iter_mut() {
+    ///         if node.as_element().map_or(false, |el| el.name.local.as_ref() == "b") {
+    ///             node.rename("strong");
+    ///         }
+    ///     }
+    /// });
+    /// assert_eq!(doc.select("p").inner_html().as_ref(), "<strong>a</strong><strong>b</strong>");
+    /// ```
+    pub fn with_nodes_mut<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut [TreeNode]) -> R,
+    {
+        let mut nodes = self.tree.nodes.borrow_mut();
+        let res = f(&mut nodes);
+        debug_assert!(
+            TreeNodeOps::links_are_consistent(&nodes),
+            "inconsistent links between nodes after `with_nodes_mut`"
+        );
+        res
+    }
+
+    /// Collapses every run of whitespace characters in the document's text nodes into a single space,
+    /// rewriting the text nodes in place.
+    ///
+    /// Text inside preformatted elements (`pre`, `textarea`) stays untouched.
+    /// Unlike [`NodeRef::inner_text`], which returns the normalized text,
+    /// it modifies the tree and keeps its structure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<div>\n  Hello,\n\t<b>World</b>  </div><pre>  a\n  b</pre>");
+    /// doc.deduplicate_whitespace();
+    /// assert_eq!(doc.select("div").html().as_ref(), "<div> Hello, <b>World</b> </div>");
+    /// assert_eq!(doc.select("pre").text().as_ref(), "  a\n  b");
+    /// ```
+    pub fn deduplicate_whitespace(&self) {
+        let text_nodes = self.text_nodes();
+        let mut nodes = self.tree.nodes.borrow_mut();
+        for node in text_nodes {
+            if is_preformatted(&nodes, &node.id) {
+                continue;
+            }
+            let Some(NodeData::Text { ref mut contents }) =
+                nodes.get_mut(node.id.value).map(|n| &mut n.data)
+            else {
+                continue;
+            };
+            let collapsed = collapse_whitespace(contents);
+            if collapsed.as_str() != contents.as_ref() {
+                *contents = wrap_tendril(StrTendril::from(collapsed));
+            }
+        }
+    }
+
+    /// Repairs double-encoded HTML entities by decoding entities in the document's text nodes
+    /// one more level, i.e. the text `&amp;` (parsed from `&amp;amp;`) becomes `&`.
+    ///
+    /// It is conservative: only complete entities terminated by `;` are decoded —
+    /// known named entities and valid numeric character references. Anything else stays as is.
+    /// The contents of `script` and `style` elements are never touched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<p>Tom &amp;amp; Jerry &amp;lt;3, AT&amp;T</p>");
+    /// doc.fix_encoding_entities();
+    /// assert_eq!(doc.select("p").text().as_ref(), "Tom & Jerry <3, AT&T");
+    /// ```
+    pub fn fix_encoding_entities(&self) {
+        let text_nodes = self.text_nodes();
+        let mut nodes = self.tree.nodes.borrow_mut();
+        for node in text_nodes {
+            let in_raw_text = nodes
+                .get(node.id.value)
+                .and_then(|n| n.parent)
+                .and_then(|parent_id| nodes.get(parent_id.value))
+                .and_then(|parent| parent.as_element())
+                .map_or(false, |el| {
+                    matches!(el.name.local, local_name!("script") | local_name!("style"))
+                });
+            if in_raw_text {
+                continue;
+            }
+            let Some(NodeData::Text { ref mut contents }) =
+                nodes.get_mut(node.id.value).map(|n| &mut n.data)
+            else {
+                continue;
+            };
+            if let Some(decoded) = decode_entities(contents) {
+                *contents = wrap_tendril(StrTendril::from(decoded));
+            }
+        }
+    }
+
+    /// Returns the delay in seconds and the target url of the first
+    /// `<meta http-equiv="refresh">` element in the document.
+    ///
+    /// The url is `None` if the `content` attribute contains only the delay,
+    /// which means that the page is reloaded.
+    /// Returns `None` if there is no such element, or its `content` attribute can't be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<meta http-equiv="refresh" content="5; url=/next">"#);
+    /// assert_eq!(doc.meta_refresh(), Some((5, Some("/next".into()))));
+    /// ```
+    pub fn meta_refresh(&self) -> Option<(u32, Option<StrTendril>)> {
+        let content = self.meta_http_equiv("refresh")?;
+        parse_meta_refresh(&content)
+    }
+
+    /// Returns the language of the document.
+    ///
+    /// This is the value of the `lang` attribute of the root `<html>` element,
+    /// or, if it is missing or empty, the first language from `<meta http-equiv="content-language">`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<html lang="en"><head></head><body></body></html>"#);
+    /// assert_eq!(doc.language(), Some("en".into()));
+    /// ```
+    pub fn language(&self) -> Option<StrTendril> {
+        let html_lang = self
+            .root()
+            .element_children()
+            .into_iter()
+            .find(|node| node.node_name().map_or(false, |name| &*name == "html"))
+            .and_then(|html| html.attr("lang"))
+            .map(|lang| StrTendril::from(lang.trim()))
+            .filter(|lang| !lang.is_empty());
+        if html_lang.is_some() {
+            return html_lang;
+        }
+
+        let content = self.meta_http_equiv("content-language")?;
+        let lang = content.split(',').next().unwrap_or_default().trim();
+        if lang.is_empty() {
+            None
+        } else {
+            Some(StrTendril::from(lang))
+        }
+    }
+
+    /// Returns the `content` attribute of the first `<meta>` element with the given `http-equiv` attribute.
+    fn meta_http_equiv(&self, name: &str) -> Option<StrTendril> {
+        self.select("meta[http-equiv][content]")
+            .iter()
+            .find(|meta| {
+                meta.attr("http-equiv")
+                    .map_or(false, |v| v.trim().eq_ignore_ascii_case(name))
+            })?
+            .attr("content")
+    }
 }
 
 // traversal methods
@@ -221,6 +762,308 @@ impl Document {
         let matcher = Matcher::new(sel).expect("Invalid CSS selector");
         self.select_single_matcher(&matcher)
     }
+
+    /// Gets the elements of the document having one of the given names, in document order.
+    /// It is a shorter alternative to a selector union like `h1, h2, h3`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<h1>Title</h1><p>Text</p><h3>Subtitle</h3>");
+    /// let headings = doc.select_tags(&["h1", "h2", "h3", "h4", "h5", "h6"]);
+    /// assert_eq!(headings.text().as_ref(), "TitleSubtitle");
+    /// ```
+    pub fn select_tags(&self, names: &[&str]) -> Selection<'_> {
+        self.select_by_tag(|name| names.contains(&name))
+    }
+
+    /// Gets the elements of the document whose names satisfy the predicate, in document order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<h1>Title</h1><p>Text</p><h3>Subtitle</h3><hr>");
+    /// let headings = doc.select_by_tag(|name| {
+    ///     name.len() == 2 && name.starts_with('h') && name[1..].parse::<u8>().is_ok()
+    /// });
+    /// assert_eq!(headings.length(), 2);
+    /// ```
+    pub fn select_by_tag<F>(&self, f: F) -> Selection<'_>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let tree_nodes = self.tree.nodes.borrow();
+        let nodes = descendant_nodes(Ref::clone(&tree_nodes), &self.tree.root_id())
+            .filter(|id| {
+                tree_nodes
+                    .get(id.value)
+                    .and_then(|node| node.as_element())
+                    .map_or(false, |el| f(el.name.local.as_ref()))
+            })
+            .map(|id| NodeRef::new(id, &self.tree))
+            .collect();
+        Selection { nodes }
+    }
+
+    /// Counts the descendants of the root document node that match the given CSS selector.
+    /// Unlike `select(sel).length()`, it doesn't collect the matched nodes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<ul><li>1</li><li>2</li></ul><ol><li>3</li></ol>"#);
+    /// assert_eq!(doc.count("li"), 3);
+    /// assert_eq!(doc.count("ul > li"), 2);
+    /// ```
+    pub fn count(&self, sel: &str) -> usize {
+        let matcher = Matcher::new(sel).expect("Invalid CSS selector");
+        self.count_matcher(&matcher)
+    }
+
+    /// Counts the descendants of the root document node that match the given matcher.
+    pub fn count_matcher(&self, matcher: &Matcher) -> usize {
+        Matches::from_one(self.tree.root(), matcher, MatchScope::IncludeNode).count()
+    }
+
+    /// Finds all descendant elements of the root document node that match the given path.
+    ///
+    /// The path is a sequence of steps, see [`NodeRef::find`] for the details.
+    ///
+    /// # Experimental
+    /// This method is experimental and may change in the future. The `path` argument will be revised.
+    pub fn find(&self, path: &[&str]) -> Vec<NodeRef<'_>> {
+        self.root().find(path)
+    }
+
+    /// Returns the combined text of all elements matching the CSS selector, joined with the separator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<p>First</p><div>Skip</div><p>Second</p>");
+    /// assert_eq!(doc.text_of_selector("p", "\n").as_ref(), "First\nSecond");
+    /// ```
+    pub fn text_of_selector(&self, sel: &str, sep: &str) -> StrTendril {
+        let mut text = StrTendril::new();
+        for (i, node) in self.select(sel).iter().enumerate() {
+            if i > 0 {
+                text.push_slice(sep);
+            }
+            text.push_tendril(&node.text());
+        }
+        text
+    }
+
+    /// Collects non-empty values of the named attribute of all elements matching the CSS selector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<a class="link" href="/a">A</a><a class="link" href="">B</a><a href="/c">C</a>"#);
+    /// assert_eq!(doc.collect_attrs(".link", "href"), vec!["/a".into()]);
+    /// ```
+    pub fn collect_attrs(&self, sel: &str, name: &str) -> Vec<StrTendril> {
+        self.select(sel)
+            .iter()
+            .filter_map(|node| node.attr(name))
+            .filter(|val| !val.is_empty())
+            .collect()
+    }
+
+    /// Returns the readable text of the main content of the document, formatted like [`NodeRef::inner_text`].
+    ///
+    /// This is a heuristic, which doesn't apply CSS. The content is taken from the first `<article>`,
+    /// `<main>`, `[role=main]` or `<body>` element, in this order. Then the following nodes are skipped:
+    /// - nodes that are not visible, see [`NodeRef::is_visible`], including scripts and styles;
+    /// - navigation, sidebars, footers and forms (`nav`, `aside`, `footer`, `form`, `header` outside of `article`, etc.);
+    /// - elements whose `class` or `id` looks like an advertisement or a banner, e.g. `ad`, `ads-top`, `banner`.
+    ///
+    /// The document itself is not changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(
+    ///     r#"<body><nav>Home | About</nav><p>First paragraph.</p><div class="ad">Buy now!</div><p>Second one.</p></body>"#,
+    /// );
+    /// assert_eq!(doc.article_text().as_ref(), "First paragraph.\n\nSecond one.");
+    /// ```
+    pub fn article_text(&self) -> StrTendril {
+        let content = ["article", "main", "[role=main]", "body"]
+            .iter()
+            .find_map(|sel| self.select_single(sel).nodes().first().cloned())
+            .unwrap_or_else(|| self.root());
+        let copy = Document::fragment_from_nodes(&[content]);
+        let boilerplate: Vec<NodeRef> = copy
+            .root()
+            .descendants_it()
+            .filter(is_boilerplate)
+            .collect();
+        for node in boilerplate {
+            node.remove_from_parent();
+        }
+        copy.root().inner_text()
+    }
+
+    /// Returns the raw text contents of all `<script type="application/ld+json">` elements in the document,
+    /// which usually hold structured data (JSON-LD). Blocks are returned in document order, as is, without parsing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(
+    ///     r#"<head><script type="application/ld+json">{"@type": "Article"}</script></head>"#,
+    /// );
+    /// assert_eq!(doc.json_ld(), vec![r#"{"@type": "Article"}"#.into()]);
+    /// ```
+    pub fn json_ld(&self) -> Vec<StrTendril> {
+        self.select("script[type]")
+            .iter()
+            .filter(|node| {
+                node.attr("type").map_or(false, |t| {
+                    t.trim().eq_ignore_ascii_case("application/ld+json")
+                })
+            })
+            .map(|node| node.text())
+            .collect()
+    }
+
+    /// Returns the parsed JSON-LD blocks of the document, see [`Document::json_ld`].
+    /// Blocks that are not valid JSON are skipped.
+    ///
+    /// Requires the `json` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(
+    ///     r#"<head><script type="application/ld+json">{"@type": "Article"}</script></head>"#,
+    /// );
+    /// let values = doc.json_ld_values();
+    /// assert_eq!(values[0]["@type"], "Article");
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn json_ld_values(&self) -> Vec<serde_json::Value> {
+        self.json_ld()
+            .iter()
+            .filter_map(|block| serde_json::from_str(block).ok())
+            .collect()
+    }
+
+    /// Splits the `href` attributes of all links in the document into internal and external ones.
+    ///
+    /// Every `href` is resolved against `base`. A link is internal if its host is the same as the host of `base`,
+    /// otherwise it is external. Only `http` and `https` links are classified: `mailto:`, `javascript:` and
+    /// unparsable links are skipped. If `base` is not a valid absolute URL, both vectors are empty.
+    ///
+    /// Requires the `url` feature.
+    ///
+    /// # Returns
+    ///
+    /// `(Vec<StrTendril>, Vec<StrTendril>)` -- a pair of internal and external resolved links in document order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<a href="/about">About</a><a href="https://example.org/">Other</a>"#);
+    /// let (internal, external) = doc.classify_links("https://example.com/blog/");
+    /// assert_eq!(internal, vec!["https://example.com/about".into()]);
+    /// assert_eq!(external, vec!["https://example.org/".into()]);
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn classify_links(&self, base: &str) -> (Vec<StrTendril>, Vec<StrTendril>) {
+        let mut internal = vec![];
+        let mut external = vec![];
+        let Ok(base) = url::Url::parse(base) else {
+            return (internal, external);
+        };
+        for (_, href) in Selection::from(self.root()).extract_links() {
+            let Ok(link) = base.join(href.trim()) else {
+                continue;
+            };
+            if !matches!(link.scheme(), "http" | "https") {
+                continue;
+            }
+            let link_str = StrTendril::from(link.as_str());
+            if link.host_str() == base.host_str() {
+                internal.push(link_str);
+            } else {
+                external.push(link_str);
+            }
+        }
+        (internal, external)
+    }
+
+    /// Resolves the (possibly relative) url against the document's base url.
+    ///
+    /// The base url is taken from the `href` attribute of the `<base>` element. If there is no `<base>`
+    /// element, the canonical url of the page (`<link rel="canonical">`) or `<meta property="og:url">`
+    /// is used as a best guess. Only an absolute base url is taken into account.
+    ///
+    /// Requires the `url` feature.
+    ///
+    /// # Returns
+    ///
+    /// The resolved absolute url, the url itself if it is already absolute,
+    /// or `None` if there is no suitable base url or the url can't be resolved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<head><base href="https://example.com/blog/"></head>"#);
+    /// assert_eq!(doc.resolve_url("post.html"), Some("https://example.com/blog/post.html".into()));
+    /// assert_eq!(doc.resolve_url("/about"), Some("https://example.com/about".into()));
+    /// ```
+    #[cfg(feature = "url")]
+    pub fn resolve_url(&self, href: &str) -> Option<StrTendril> {
+        let href = href.trim();
+        if let Ok(url) = url::Url::parse(href) {
+            return Some(StrTendril::from(url.as_str()));
+        }
+        let base = self
+            .base_uri()
+            .into_iter()
+            .chain(self.select_single(r#"link[rel="canonical"]"#).attr("href"))
+            .chain(
+                self.select_single(r#"meta[property="og:url"]"#)
+                    .attr("content"),
+            )
+            .find_map(|base| url::Url::parse(base.trim()).ok())?;
+        let url = base.join(href).ok()?;
+        Some(StrTendril::from(url.as_str()))
+    }
 }
 
 impl TreeSink for Document {
@@ -457,3 +1300,162 @@ fn append_to_existing_text(prev: &mut TreeNode, text: &str) -> bool {
         _ => false,
     }
 }
+
+/// Elements that don't belong to the main content of a page.
+static BOILERPLATE_ELEMENTS: &[&str] = &[
+    "nav", "aside", "footer", "form", "noscript", "iframe", "button", "select", "dialog",
+];
+
+/// Parts of `class` and `id` values that mark advertisements, banners and other non-content elements.
+static BOILERPLATE_MARKERS: &[&str] = &[
+    "ad",
+    "ads",
+    "advert",
+    "advertisement",
+    "banner",
+    "sponsored",
+    "promo",
+    "sidebar",
+    "cookie",
+];
+
+/// Checks if the node is not a part of the main content of a page, see [`Document::article_text`].
+fn is_boilerplate(node: &NodeRef) -> bool {
+    if !node.is_element() {
+        return false;
+    }
+    if !node.is_visible() {
+        return true;
+    }
+    let Some(name) = node.node_name() else {
+        return false;
+    };
+    if BOILERPLATE_ELEMENTS.contains(&name.as_ref()) {
+        return true;
+    }
+    if name.as_ref() == "header"
+        && !node
+            .ancestors_it(None)
+            .any(|a| a.node_name().map_or(false, |n| n.as_ref() == "article"))
+    {
+        return true;
+    }
+    if node.attr("role").map_or(false, |role| {
+        ["navigation", "banner", "contentinfo", "complementary"].contains(&role.as_ref())
+    }) {
+        return true;
+    }
+    ["class", "id"].iter().any(|attr| {
+        node.attr(attr).map_or(false, |val| {
+            val.split_whitespace()
+                .flat_map(|token| token.split(['-', '_']))
+                .any(|part| {
+                    BOILERPLATE_MARKERS
+                        .iter()
+                        .any(|marker| part.eq_ignore_ascii_case(marker))
+                })
+        })
+    })
+}
+
+/// Parses the `content` attribute of `<meta http-equiv="refresh">`, e.g. `5; url=/next`.
+fn parse_meta_refresh(content: &str) -> Option<(u32, Option<StrTendril>)> {
+    let content = content.trim_start();
+    let delay_end = content
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(content.len());
+    let delay: u32 = content[..delay_end].parse().ok()?;
+
+    // fractional part of the delay is ignored
+    let rest = content[delay_end..].trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let rest = rest.trim_start();
+    let rest = match rest.strip_prefix([';', ',']) {
+        Some(rest) => rest.trim_start(),
+        None if rest.is_empty() => return Some((delay, None)),
+        None => return None,
+    };
+
+    let mut url = rest;
+    if url
+        .get(..3)
+        .map_or(false, |key| key.eq_ignore_ascii_case("url"))
+    {
+        let after_key = url[3..].trim_start();
+        if let Some(value) = after_key.strip_prefix('=') {
+            url = value.trim_start();
+        }
+    }
+
+    let url = match url.chars().next() {
+        Some(quote @ ('\'' | '"')) => {
+            let value = &url[1..];
+            value.split(quote).next().unwrap_or(value)
+        }
+        _ => url,
+    }
+    .trim();
+
+    if url.is_empty() {
+        Some((delay, None))
+    } else {
+        Some((delay, Some(StrTendril::from(url))))
+    }
+}
+
+/// Decodes complete HTML entities (`&name;`, `&#NN;`, `&#xHH;`) in the text.
+/// Returns `None` if nothing was decoded.
+fn decode_entities(text: &str) -> Option<String> {
+    if !text.contains('&') {
+        return None;
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut changed = false;
+    while let Some(pos) = rest.find('&') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        match decode_entity(rest) {
+            Some((chars, len)) => {
+                out.extend(chars.into_iter().flatten());
+                rest = &rest[len..];
+                changed = true;
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    changed.then_some(out)
+}
+
+/// Decodes a single entity at the start of `s`, returning its characters and its length in bytes.
+fn decode_entity(s: &str) -> Option<([Option<char>; 2], usize)> {
+    // the longest named entity is `&CounterClockwiseContourIntegral;`
+    let end = s.get(..34).unwrap_or(s).find(';')?;
+    let body = &s[1..end];
+    if let Some(num) = body.strip_prefix('#') {
+        let (digits, radix) = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => (hex, 16),
+            None => (num, 10),
+        };
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return None;
+        }
+        let c = u32::from_str_radix(digits, radix)
+            .ok()
+            .and_then(char::from_u32)
+            .filter(|c| *c != '\0')?;
+        return Some(([Some(c), None], end + 1));
+    }
+    if body.is_empty() || !body.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    let &(cp1, cp2) = html5ever::data::NAMED_ENTITIES.get(&s[1..=end])?;
+    let c1 = char::from_u32(cp1).filter(|c| *c != '\0')?;
+    Some((
+        [Some(c1), char::from_u32(cp2).filter(|c| *c != '\0')],
+        end + 1,
+    ))
+}
@@ -111,6 +111,58 @@ impl TreeNodeOps {
         }
         None
     }
+
+    /// Checks that the links between nodes are consistent: every node is stored at the index of its id,
+    /// all links point to existing nodes, siblings link to each other in both directions, and
+    /// every child list, walked from `first_child`, belongs to its parent and ends with `last_child`.
+    pub(crate) fn links_are_consistent(nodes: &[TreeNode]) -> bool {
+        let exists = |id: Option<NodeId>| id.map_or(true, |id| id.value < nodes.len());
+        let mut linked_children = 0;
+
+        for (i, node) in nodes.iter().enumerate() {
+            if node.id.value != i
+                || ![
+                    node.parent,
+                    node.prev_sibling,
+                    node.next_sibling,
+                    node.first_child,
+                    node.last_child,
+                ]
+                .into_iter()
+                .all(exists)
+            {
+                return false;
+            }
+            if node
+                .next_sibling
+                .map_or(false, |id| nodes[id.value].prev_sibling != Some(node.id))
+                || node
+                    .prev_sibling
+                    .map_or(false, |id| nodes[id.value].next_sibling != Some(node.id))
+            {
+                return false;
+            }
+
+            let mut prev = None;
+            let mut next_child_id = node.first_child;
+            while let Some(child_id) = next_child_id {
+                let child = &nodes[child_id.value];
+                linked_children += 1;
+                // a cycle in the child list would make it longer than the arena
+                if child.parent != Some(node.id) || linked_children > nodes.len() {
+                    return false;
+                }
+                prev = Some(child_id);
+                next_child_id = child.next_sibling;
+            }
+            if node.last_child != prev {
+                return false;
+            }
+        }
+
+        // every node with a parent must have been reached from its parent's child list
+        linked_children == nodes.iter().filter(|node| node.parent.is_some()).count()
+    }
 }
 
 // manipulation
@@ -313,6 +365,36 @@ impl TreeNodeOps {
         }
     }
 
+    /// Wraps the selected node with the first element among the node by `wrapper_id` and it's siblings.
+    /// The selected node is placed into the innermost first element of the wrapper.
+    /// If there is no element among the nodes, the selected node remains unchanged.
+    pub fn wrap_with(nodes: &mut [TreeNode], id: &NodeId, wrapper_id: &NodeId) {
+        let mut next_node_id = Some(*wrapper_id);
+        let mut wrapper_id = None;
+        while let Some(node_id) = next_node_id {
+            let Some(node) = nodes.get(node_id.value) else {
+                break;
+            };
+            if node.is_element() {
+                wrapper_id = Some(node_id);
+                break;
+            }
+            next_node_id = node.next_sibling;
+        }
+        let Some(wrapper_id) = wrapper_id else {
+            return;
+        };
+
+        let mut inner_id = wrapper_id;
+        while let Some(child_id) = Self::first_element_child_of(nodes, &inner_id) {
+            inner_id = child_id;
+        }
+
+        Self::insert_before_of(nodes, id, &wrapper_id);
+        Self::remove_from_parent(nodes, id);
+        Self::append_child_of(nodes, &inner_id, id);
+    }
+
     /// Remove a node from the its parent by id. The node remains in the tree.
     /// It is possible to assign it to another node in the tree after this operation.
     pub fn remove_from_parent(nodes: &mut [TreeNode], id: &NodeId) {
@@ -77,11 +77,13 @@ impl Traversal {
     ///
     /// * `nodes` - The nodes of the tree.
     /// * `id` - The id of the starting node.
-    /// * `path` - The sequence of element names to search for. Currently, only element names are supported.
+    /// * `path` - The sequence of path steps to search for. Each step is either an element name (`div`),
+    ///   a wildcard matching any element (`*`), optionally followed by an attribute predicate:
+    ///   `a[@href]` matches `a` elements that have the `href` attribute, `*[@id]` matches any element with `id`.
     ///
     /// # Returns
     ///
-    /// A list of ids of all descendant elements that have the given names.
+    /// A list of ids of all descendant elements that match the given path.
     ///
     /// # Experimental
     ///
@@ -106,14 +108,11 @@ impl Traversal {
                     // Since these nodes are descendants of the primary node and
                     // were previously extracted from the `Tree` with only elements remaining,
                     // `else` case should be unreachable.
-                    let Some(node_name) = nodes
-                        .get(node_id.value)
-                        .and_then(|n| n.as_element().map(|el| el.node_name()))
-                    else {
+                    let Some(node) = nodes.get(node_id.value) else {
                         continue;
                     };
 
-                    if node_name.as_ref() == *name {
+                    if Self::is_path_step_match(node, name) {
                         candidates.push(node_id);
                         continue;
                     }
@@ -133,4 +132,28 @@ impl Traversal {
         }
         res
     }
+
+    /// Checks if the tree node is an element that matches the path step.
+    ///
+    /// A step is an element name or `*`, optionally followed by an attribute predicate `[@name]`.
+    /// The step is parsed in place, without allocations.
+    fn is_path_step_match(node: &TreeNode, step: &str) -> bool {
+        let Some(el) = node.as_element() else {
+            return false;
+        };
+
+        let (name, attr) = match step.split_once("[@") {
+            Some((name, predicate)) => match predicate.strip_suffix(']') {
+                Some(attr) => (name, Some(attr)),
+                // a malformed predicate never matches
+                None => return false,
+            },
+            None => (step, None),
+        };
+
+        if name != "*" && el.name.local.as_ref() != name {
+            return false;
+        }
+        attr.map_or(true, |attr| el.has_attr(attr))
+    }
 }
@@ -55,6 +55,30 @@ impl Tree {
         NodeRef { id, tree: self }
     }
 
+    /// Creates a new comment node with the given text, without parent
+    pub fn new_comment<T: Into<StrTendril>>(&self, text: T) -> NodeRef<'_> {
+        let text = text.into();
+        let id = self.create_node(NodeData::Comment {
+            contents: wrap_tendril(text),
+        });
+        NodeRef { id, tree: self }
+    }
+
+    /// Creates a new doctype node with the given name, public id and system id, without parent
+    pub fn new_doctype<T: Into<StrTendril>>(
+        &self,
+        name: T,
+        public_id: T,
+        system_id: T,
+    ) -> NodeRef<'_> {
+        let id = self.create_node(NodeData::Doctype {
+            name: wrap_tendril(name.into()),
+            public_id: wrap_tendril(public_id.into()),
+            system_id: wrap_tendril(system_id.into()),
+        });
+        NodeRef { id, tree: self }
+    }
+
     /// Gets node's name by by id
     pub fn get_name<'a>(&'a self, id: &NodeId) -> Option<Ref<'a, QualName>> {
         Ref::filter_map(self.nodes.borrow(), |nodes| {
@@ -106,6 +130,15 @@ impl Tree {
         TreeNodeOps::create_node(nodes.deref_mut(), data)
     }
 
+    /// Shrinks the capacity of the underlying node storage as much as possible.
+    ///
+    /// Parsing and manipulations may leave excess capacity in the tree,
+    /// this method releases it, when the tree is not going to grow anymore.
+    /// Nodes are never removed from the tree, so it doesn't affect existing node ids.
+    pub fn shrink_to_fit(&self) {
+        self.nodes.borrow_mut().shrink_to_fit();
+    }
+
     /// Gets node by id
     pub fn get(&self, id: &NodeId) -> Option<NodeRef> {
         let nodes = self.nodes.borrow();
@@ -533,6 +566,24 @@ mod tests {
             .exists());
     }
 
+    #[test]
+    fn test_shrink_to_fit() {
+        let doc = Document::from(CONTENTS);
+        let tree = &doc.tree;
+        tree.nodes.borrow_mut().reserve(1024);
+        let len = tree.nodes.borrow().len();
+        assert!(tree.nodes.borrow().capacity() >= len + 1024);
+
+        tree.shrink_to_fit();
+        let nodes = tree.nodes.borrow();
+        assert_eq!(nodes.len(), len);
+        assert!(nodes.capacity() < len + 1024);
+        drop(nodes);
+
+        // the tree is still usable
+        assert_eq!(doc.select("#first-child").text(), "foo".into());
+    }
+
     #[allow(deprecated)]
     #[test]
     fn test_append_prev_sibling_of() {
@@ -23,11 +23,13 @@ mod matcher;
 mod node;
 mod selection;
 
-pub use document::Document;
+pub use document::{Document, DocumentStats};
 pub use dom_tree::Tree;
 pub use dom_tree::TreeNodeOps;
 pub use matcher::Matcher;
 #[doc(hidden)]
 pub use node::SerializableNodeRef;
-pub use node::{Element, Node, NodeData, NodeId, NodeIdProver, NodeRef};
+pub use node::{
+    Dir, Element, InsertPosition, Node, NodeContent, NodeData, NodeId, NodeIdProver, NodeRef,
+};
 pub use selection::Selection;
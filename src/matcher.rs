@@ -1,6 +1,6 @@
 use std::{fmt, iter};
 
-use cssparser::{CowRcStr, ParseError, SourceLocation, ToCss};
+use cssparser::{CowRcStr, ParseError, ParseErrorKind, SourceLocation, ToCss};
 use html5ever::Namespace;
 use selectors::context::SelectorCaches;
 use selectors::parser::{self, SelectorList, SelectorParseErrorKind};
@@ -29,6 +29,52 @@ impl Matcher {
         .map(|selector_list| Matcher { selector_list })
     }
 
+    /// Checks if the string is a valid CSS selector, without keeping the parsed matcher.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Matcher;
+    ///
+    /// assert!(Matcher::is_valid("div > a[href]"));
+    /// assert!(!Matcher::is_valid("div >"));
+    /// ```
+    pub fn is_valid(sel: &str) -> bool {
+        Matcher::new(sel).is_ok()
+    }
+
+    /// Returns a description of the error that occurs while parsing the CSS selector,
+    /// including its location, or `None` if the selector is valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Matcher;
+    ///
+    /// assert_eq!(Matcher::parse_error("div"), None);
+    /// assert_eq!(
+    ///     Matcher::parse_error("div >").unwrap(),
+    ///     "combinator is not followed by a selector (line 1, column 6)"
+    /// );
+    /// assert_eq!(
+    ///     Matcher::parse_error("div:foo").unwrap(),
+    ///     "unsupported pseudo-class or pseudo-element: 'foo' (line 1, column 5)"
+    /// );
+    /// ```
+    pub fn parse_error(sel: &str) -> Option<String> {
+        let err = Matcher::new(sel).err()?;
+        let kind = match err.kind {
+            ParseErrorKind::Basic(kind) => kind.to_string(),
+            ParseErrorKind::Custom(kind) => describe_selector_error(&kind),
+        };
+        Some(format!(
+            "{} (line {}, column {})",
+            kind,
+            err.location.line + 1,
+            err.location.column
+        ))
+    }
+
     /// Checks if an element matches Matcher's selection.
     pub fn match_element<E>(&self, element: &E) -> bool
     where
@@ -41,22 +87,42 @@ impl Matcher {
 
     /// Checks if an element matches Matcher's selection.
     pub fn match_element_with_caches<E>(&self, element: &E, caches: &mut SelectorCaches) -> bool
+    where
+        E: Element<Impl = InnerSelector>,
+    {
+        self.match_element_with_scope(element, None, caches)
+    }
+
+    /// Checks if an element matches Matcher's selection.
+    /// The `:scope` pseudo-class refers to the `scope` element, if it is provided,
+    /// otherwise it refers to the root of the document.
+    pub(crate) fn match_element_with_scope<E>(
+        &self,
+        element: &E,
+        scope: Option<&E>,
+        caches: &mut SelectorCaches,
+    ) -> bool
     where
         E: Element<Impl = InnerSelector>,
     {
         let mut ctx = get_matching_context(caches);
+        ctx.scope_element = scope.map(|e| e.opaque());
         matching::matches_selector_list(&self.selector_list, element, &mut ctx)
     }
 }
 
 pub struct Matches<'a, 'b> {
-    nodes: Vec<NodeRef<'a>>,
+    /// Nodes to check, each with the element that `:scope` refers to.
+    nodes: Vec<(NodeRef<'a>, Option<NodeRef<'a>>)>,
     matcher: &'b Matcher,
     set: InnerHashSet<usize>,
     caches: SelectorCaches,
 }
 
 /// Telling a `matches` if we want to skip the roots.
+///
+/// With `ChildrenOnly` the `:scope` pseudo-class refers to the root node (if it is an element),
+/// so `:scope > li` matches only direct `li` children of the root.
 #[derive(Debug, Clone)]
 pub enum MatchScope {
     IncludeNode,
@@ -67,11 +133,20 @@ impl<'a, 'b> Matches<'a, 'b> {
     fn nodes_from_root<I: Iterator<Item = NodeRef<'a>>>(
         root_nodes: I,
         match_scope: MatchScope,
-    ) -> Vec<NodeRef<'a>> {
+    ) -> Vec<(NodeRef<'a>, Option<NodeRef<'a>>)> {
         match match_scope {
-            MatchScope::IncludeNode => root_nodes.collect(),
+            MatchScope::IncludeNode => root_nodes.map(|node| (node, None)).collect(),
             MatchScope::ChildrenOnly => root_nodes
-                .flat_map(|node| node.children_it(true).filter(|n| n.is_element()))
+                .flat_map(|node| {
+                    let scope = if node.is_element() {
+                        Some(node.clone())
+                    } else {
+                        None
+                    };
+                    node.children_it(true)
+                        .filter(|n| n.is_element())
+                        .map(move |n| (n, scope.clone()))
+                })
                 .collect(),
         }
     }
@@ -105,16 +180,19 @@ impl<'a> Iterator for Matches<'a, '_> {
     type Item = NodeRef<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(node) = self.nodes.pop() {
+        while let Some((node, scope)) = self.nodes.pop() {
             if self.set.contains(&node.id.value) {
                 continue;
             }
-            self.nodes
-                .extend(node.children_it(true).filter(|n| n.is_element()));
+            self.nodes.extend(
+                node.children_it(true)
+                    .filter(|n| n.is_element())
+                    .map(|n| (n, scope.clone())),
+            );
 
             if self
                 .matcher
-                .match_element_with_caches(&node, &mut self.caches)
+                .match_element_with_scope(&node, scope.as_ref(), &mut self.caches)
             {
                 self.set.insert(node.id.value);
                 return Some(node);
@@ -164,6 +242,10 @@ impl<'i> parser::Parser<'i> for InnerSelectorParser {
             Ok(Checked)
         } else if name.eq_ignore_ascii_case("indeterminate") {
             Ok(Indeterminate)
+        } else if name.eq_ignore_ascii_case("required") {
+            Ok(Required)
+        } else if name.eq_ignore_ascii_case("optional") {
+            Ok(Optional)
         } else if name.eq_ignore_ascii_case("only-text") {
             Ok(OnlyText)
         } else {
@@ -228,10 +310,17 @@ pub enum NonTSPseudoClass {
     Active,
     Focus,
     Hover,
+    /// `:enabled` means a form control that is not disabled.
     Enabled,
+    /// `:disabled` means a form control having the `disabled` attribute or placed inside a disabled `fieldset`.
     Disabled,
+    /// `:checked` means a checkbox or a radio button having the `checked` attribute, or an `option` having the `selected` attribute.
     Checked,
     Indeterminate,
+    /// `:required` means an `input`, `select` or `textarea` element having the `required` attribute.
+    Required,
+    /// `:optional` means an `input`, `select` or `textarea` element without the `required` attribute.
+    Optional,
     /// `:only-text` pseudo-class allows selecting a node with no child elements except a single **text** child node.
     OnlyText,
     /// `:has-text` pseudo-class represents a selection for the element or one of its descendant element that contains the specified text.
@@ -256,6 +345,8 @@ impl ToCss for NonTSPseudoClass {
             NonTSPseudoClass::Disabled => dest.write_str(":disabled"),
             NonTSPseudoClass::Checked => dest.write_str(":checked"),
             NonTSPseudoClass::Indeterminate => dest.write_str(":indeterminate"),
+            NonTSPseudoClass::Required => dest.write_str(":required"),
+            NonTSPseudoClass::Optional => dest.write_str(":optional"),
             NonTSPseudoClass::OnlyText => dest.write_str(":only-text"),
             NonTSPseudoClass::HasText(s) => {
                 dest.write_str(":has-text(")?;
@@ -327,3 +418,55 @@ fn get_matching_context(
     );
     ctx
 }
+
+/// Returns a human-readable description of a selector parse error kind,
+/// in the same manner as the `Display` implementation of `BasicParseErrorKind`.
+fn describe_selector_error(kind: &SelectorParseErrorKind) -> String {
+    use SelectorParseErrorKind::*;
+    match kind {
+        NoQualifiedNameInAttributeSelector(t) => {
+            format!(
+                "expected an attribute name in attribute selector, found: {:?}",
+                t
+            )
+        }
+        EmptySelector => "empty selector".to_string(),
+        DanglingCombinator => "combinator is not followed by a selector".to_string(),
+        NonCompoundSelector => "expected a compound selector".to_string(),
+        NonPseudoElementAfterSlotted => {
+            "only pseudo-elements are allowed after ::slotted()".to_string()
+        }
+        InvalidPseudoElementAfterSlotted => "invalid pseudo-element after ::slotted()".to_string(),
+        InvalidPseudoElementInsideWhere => "invalid pseudo-element inside :where()".to_string(),
+        InvalidState => "invalid state".to_string(),
+        UnexpectedTokenInAttributeSelector(t) => {
+            format!("unexpected token in attribute selector: {:?}", t)
+        }
+        PseudoElementExpectedColon(t) => {
+            format!("expected ':' for a pseudo-element, found: {:?}", t)
+        }
+        PseudoElementExpectedIdent(t) => {
+            format!("expected a pseudo-element name, found: {:?}", t)
+        }
+        NoIdentForPseudo(t) => format!("expected a pseudo-class name, found: {:?}", t),
+        UnsupportedPseudoClassOrElement(name) => {
+            format!("unsupported pseudo-class or pseudo-element: '{}'", name)
+        }
+        UnexpectedIdent(ident) => format!("unexpected identifier: '{}'", ident),
+        ExpectedNamespace(prefix) => format!("unknown namespace prefix: '{}'", prefix),
+        ExpectedBarInAttr(t) => {
+            format!(
+                "expected '|' in namespaced attribute selector, found: {:?}",
+                t
+            )
+        }
+        BadValueInAttr(t) => format!("invalid value in attribute selector: {:?}", t),
+        InvalidQualNameInAttr(t) => {
+            format!("invalid qualified name in attribute selector: {:?}", t)
+        }
+        ExplicitNamespaceUnexpectedToken(t) => {
+            format!("unexpected token after namespace prefix: {:?}", t)
+        }
+        ClassNeedsIdent(t) => format!("expected a class name after '.', found: {:?}", t),
+    }
+}
@@ -5,6 +5,7 @@ mod node_data;
 mod node_ref;
 mod selector;
 mod serializing;
+mod text_formatting;
 
 use std::fmt::Debug;
 
@@ -14,8 +15,9 @@ pub use iters::{
     ancestor_nodes, child_nodes, descendant_nodes, AncestorNodes, ChildNodes, DescendantNodes,
 };
 pub use node_data::{Element, NodeData};
-pub use node_ref::{Node, NodeRef};
+pub use node_ref::{Dir, InsertPosition, Node, NodeContent, NodeRef};
 pub use serializing::SerializableNodeRef;
+pub(crate) use text_formatting::{collapse_whitespace, is_preformatted, normalized_char_count};
 
 /// Represents a Node ID.
 #[derive(Copy, Debug, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -106,6 +106,13 @@ impl TreeNode {
         }
     }
 
+    /// Sets the values of the specified attributes to the node.
+    pub fn set_attrs(&mut self, attrs: &[(&str, &str)]) {
+        if let Some(element) = self.as_element_mut() {
+            element.set_attrs(attrs);
+        }
+    }
+
     /// Removes the specified attribute from the element.
     pub fn remove_attr(&mut self, name: &str) {
         if let Some(element) = self.as_element_mut() {
@@ -147,6 +154,20 @@ impl TreeNode {
             element.remove_class(class);
         }
     }
+
+    /// Replaces a class on the node
+    pub fn replace_class(&mut self, old: &str, new: &str) {
+        if let Some(element) = self.as_element_mut() {
+            element.replace_class(old, new);
+        }
+    }
+
+    /// Toggles a class on the node
+    pub fn toggle_class(&mut self, class: &str) {
+        if let Some(element) = self.as_element_mut() {
+            element.toggle_class(class);
+        }
+    }
 }
 
 impl Clone for TreeNode {
@@ -144,6 +144,7 @@ pub fn ancestor_nodes<'a>(
 /// An iterator over the descendants of a node.
 pub struct DescendantNodes<'a> {
     nodes: Ref<'a, Vec<TreeNode>>,
+    root_id: NodeId,
     next_child_id: Option<NodeId>,
 }
 
@@ -163,6 +164,7 @@ impl<'a> DescendantNodes<'a> {
 
         DescendantNodes {
             nodes,
+            root_id: *node_id,
             next_child_id,
         }
     }
@@ -176,6 +178,10 @@ impl<'a> DescendantNodes<'a> {
         } else {
             let mut parent = node.parent;
             while let Some(parent_node) = parent.and_then(|id| self.nodes.get(id.value)) {
+                // the traversal must not leave the subtree of the starting node
+                if parent_node.id == self.root_id {
+                    return None;
+                }
                 if parent_node.next_sibling.is_some() {
                     return parent_node.next_sibling;
                 } else {
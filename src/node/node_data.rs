@@ -92,6 +92,22 @@ impl Element {
             .map_or(false, |attr| contains_class(&attr.value, class))
     }
 
+    /// Whether the element has all the given classes.
+    /// Entries containing whitespace are split, so `["btn primary"]` is the same as `["btn", "primary"]`.
+    /// An empty slice returns `true`.
+    pub fn has_classes(&self, classes: &[&str]) -> bool {
+        let value = self
+            .attrs
+            .iter()
+            .find(|a| a.name.local == local_name!("class"))
+            .map(|a| a.value.deref())
+            .unwrap_or_default();
+        classes
+            .iter()
+            .flat_map(|c| c.split_whitespace())
+            .all(|class| contains_class(value, class))
+    }
+
     /// Whether the element has the given class.
     pub fn has_class_bytes(&self, name: &[u8], case_sensitivity: CaseSensitivity) -> bool {
         self.attrs
@@ -175,6 +191,61 @@ impl Element {
         }
     }
 
+    /// Toggles a class on the element: removes it if present, adds it otherwise.
+    /// Multiple class names can be specified, separated by whitespace; each one is toggled independently.
+    pub fn toggle_class(&mut self, classes: &str) {
+        let mut toggles: Vec<&str> = Vec::new();
+        for class in classes.split_whitespace() {
+            if !toggles.contains(&class) {
+                toggles.push(class);
+            }
+        }
+        if toggles.is_empty() {
+            return;
+        }
+
+        let current = self.attr("class").unwrap_or_default();
+        let mut tokens: Vec<&str> = current.split_whitespace().collect();
+        for class in toggles {
+            if tokens.contains(&class) {
+                tokens.retain(|c| *c != class);
+            } else {
+                tokens.push(class);
+            }
+        }
+        self.set_attr("class", &tokens.join(" "));
+    }
+
+    /// Replaces the class `old` with `new` if the element has `old`, keeping the order of other classes.
+    /// The new class takes the position of the old one, unless it is already present.
+    /// Does nothing if `old` is absent or if either class name is empty.
+    pub fn replace_class(&mut self, old: &str, new: &str) {
+        let (old, new) = (old.trim(), new.trim());
+        if old.is_empty() || new.is_empty() {
+            return;
+        }
+
+        let Some(current) = self.attr("class") else {
+            return;
+        };
+        if !contains_class(&current, old) {
+            return;
+        }
+
+        let has_new = contains_class(&current, new);
+        let tokens: Vec<&str> = current
+            .split_whitespace()
+            .filter_map(|c| {
+                if c == old {
+                    (!has_new).then_some(new)
+                } else {
+                    Some(c)
+                }
+            })
+            .collect();
+        self.set_attr("class", &tokens.join(" "));
+    }
+
     /// Gets the specified attribute's value.
     pub fn attr(&self, name: &str) -> Option<StrTendril> {
         self.attrs
@@ -205,6 +276,14 @@ impl Element {
         self.attrs.retain(|attr| &attr.name.local != name);
     }
 
+    /// Sets the values of the specified attributes, overwriting existing ones.
+    /// If a name occurs more than once, the last value wins.
+    pub fn set_attrs(&mut self, attrs: &[(&str, &str)]) {
+        for (name, val) in attrs {
+            self.set_attr(name, val);
+        }
+    }
+
     /// Removes the specified attributes from the element.
     pub fn remove_attrs(&mut self, names: &[&str]) {
         self.attrs.retain(|attr| {
@@ -1,31 +1,96 @@
 use std::cell::Ref;
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::io;
 use std::ops::Deref;
 use std::ops::DerefMut;
+use std::str::FromStr;
 
 use html5ever::serialize;
-use html5ever::serialize::SerializeOpts;
 use html5ever::serialize::TraversalScope;
-use html5ever::Attribute;
+use html5ever::serialize::{Serialize, SerializeOpts};
+use html5ever::{local_name, Attribute};
 
 use tendril::StrTendril;
 
 use crate::dom_tree::Traversal;
-use crate::entities::copy_attrs;
+use crate::entities::{copy_attrs, into_tendril};
+use crate::matcher::{MatchScope, Matches};
 use crate::Document;
 use crate::Matcher;
+use crate::Selection;
 use crate::Tree;
 use crate::TreeNodeOps;
 
-use super::child_nodes;
 use super::id_provider::NodeIdProver;
 use super::inner::TreeNode;
 use super::node_data::NodeData;
-use super::serializing::SerializableNodeRef;
+use super::serializing::{PrettySerializableNodeRef, SerializableNodeRef};
+use super::text_formatting::{escape_html, format_text, is_formatting_whitespace, is_inline_node};
 use super::NodeId;
+use super::{child_nodes, descendant_nodes};
 
 pub type Node<'a> = NodeRef<'a>;
 
+/// The position of a node relative to its new parent, used by [`NodeRef::move_to`].
+#[derive(Clone, Debug)]
+pub enum InsertPosition<'a> {
+    /// As the first child of the parent.
+    First,
+    /// As the last child of the parent.
+    Last,
+    /// Right before the given child of the parent.
+    Before(NodeRef<'a>),
+    /// Right after the given child of the parent.
+    After(NodeRef<'a>),
+}
+
+/// The text direction of a node, resolved by [`NodeRef::text_direction`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dir {
+    /// Left-to-right text.
+    #[default]
+    Ltr,
+    /// Right-to-left text.
+    Rtl,
+    /// The direction is determined by the text content.
+    Auto,
+}
+
+/// The content that replaces the children of a node in [`NodeRef::replace_children`].
+///
+/// It can be created from an HTML string (`&str`, `String`, `StrTendril`) or from a `&Selection`.
+pub enum NodeContent<'a> {
+    /// HTML to parse.
+    Html(StrTendril),
+    /// Nodes to copy.
+    Selection(&'a Selection<'a>),
+}
+
+impl From<&str> for NodeContent<'_> {
+    fn from(html: &str) -> Self {
+        NodeContent::Html(html.into())
+    }
+}
+
+impl From<String> for NodeContent<'_> {
+    fn from(html: String) -> Self {
+        NodeContent::Html(html.into())
+    }
+}
+
+impl From<StrTendril> for NodeContent<'_> {
+    fn from(html: StrTendril) -> Self {
+        NodeContent::Html(html)
+    }
+}
+
+impl<'a> From<&'a Selection<'a>> for NodeContent<'a> {
+    fn from(sel: &'a Selection<'a>) -> Self {
+        NodeContent::Selection(sel)
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Represents a reference to a node in the tree.
 /// It keeps a node id and a reference to the tree,
@@ -116,6 +181,66 @@ impl<'a> NodeRef<'a> {
             .map(|n| NodeRef::new(n, self.tree))
     }
 
+    /// Returns the iterator ancestor nodes of the selected node, which yields ancestors
+    /// while the predicate returns `true`.
+    ///
+    /// # Arguments
+    /// * `pred` - The predicate that checks an ancestor. The iteration stops at the first ancestor that doesn't match.
+    ///
+    /// # Returns
+    /// impl Iterator<Item = Self>
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(
+    ///     r#"<section class="post"><div><p><b id="target">Text</b></p></div></section>"#,
+    /// );
+    /// let sel = doc.select("#target");
+    /// let node = sel.nodes().first().unwrap();
+    /// let names: Vec<_> = node
+    ///     .ancestors_while(|n| !n.has_class("post"))
+    ///     .filter_map(|n| n.node_name())
+    ///     .collect();
+    /// assert_eq!(names, vec!["p".into(), "div".into()]);
+    /// ```
+    #[inline]
+    pub fn ancestors_while<P>(&self, pred: P) -> impl Iterator<Item = Self>
+    where
+        P: Fn(&NodeRef) -> bool,
+    {
+        self.ancestors_it(None).take_while(move |n| pred(n))
+    }
+
+    /// Returns the nearest block-level element among the node and its ancestors,
+    /// e.g. a `p` or a `div` (but also a `td` or `body`), which bounds the paragraph the node belongs to.
+    /// It uses the same classification as [`NodeRef::wrap_if_inline`] and [`NodeRef::inner_text`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div><p>Some <b><i id="target">text</i></b></p></div>"#);
+    /// let sel = doc.select("#target");
+    /// let node = sel.nodes().first().unwrap();
+    /// let block = node.closest_block().unwrap();
+    /// assert_eq!(block.node_name().unwrap().as_ref(), "p");
+    /// ```
+    pub fn closest_block(&self) -> Option<Self> {
+        let nodes = self.tree.nodes.borrow();
+        let mut next_id = Some(self.id);
+        while let Some(node) = next_id.and_then(|id| nodes.get(id.value)) {
+            if node.is_element() && !is_inline_node(node) {
+                return Some(NodeRef::new(node.id, self.tree));
+            }
+            next_id = node.parent;
+        }
+        None
+    }
+
     /// Returns the descendant nodes of the selected node.
     ///
     /// # Returns
@@ -136,6 +261,38 @@ impl<'a> NodeRef<'a> {
             .map(|n| NodeRef::new(n, self.tree))
     }
 
+    /// Returns the descendant elements of the selected node, that match the given CSS selector, in document order.
+    ///
+    /// It is the node-level equivalent of [`Selection::select`], but it returns a plain vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"><p class="x">1</p><div><p class="x">2</p></div></div>"#);
+    /// let sel = doc.select("#main");
+    /// let main = sel.nodes().first().unwrap();
+    /// let found = main.descendants_matching("p.x");
+    /// assert_eq!(found.len(), 2);
+    /// assert_eq!(found[1].text().as_ref(), "2");
+    /// ```
+    pub fn descendants_matching(&self, sel: &str) -> Vec<Self> {
+        let matcher = Matcher::new(sel).expect("Invalid CSS selector");
+        Matches::from_one(self.clone(), &matcher, MatchScope::ChildrenOnly).collect()
+    }
+
+    /// Returns the descendant elements of the selected node, that match the given CSS selector, in document order.
+    /// Returns `None` if failed to parse the selector.
+    pub fn try_descendants_matching(&self, sel: &str) -> Option<Vec<Self>> {
+        let matcher = Matcher::new(sel).ok()?;
+        Some(Matches::from_one(self.clone(), &matcher, MatchScope::ChildrenOnly).collect())
+    }
+
     /// Returns the first child node of the selected node.
     #[inline]
     pub fn first_child(&self) -> Option<Self> {
@@ -160,6 +317,30 @@ impl<'a> NodeRef<'a> {
         self.tree.prev_sibling_of(&self.id)
     }
 
+    /// Returns the contents of the next sibling node, if it is a text node.
+    ///
+    /// It is useful for "label: value" patterns, where the value is a text node following an element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p><strong>Price:</strong> 25 EUR</p>"#);
+    /// let sel = doc.select("strong");
+    /// let label = sel.nodes().first().unwrap();
+    /// assert_eq!(label.next_text(), Some(" 25 EUR".into()));
+    /// assert_eq!(label.previous_text(), None);
+    /// ```
+    pub fn next_text(&self) -> Option<StrTendril> {
+        self.next_sibling().and_then(|node| node.text_contents())
+    }
+
+    /// Returns the contents of the previous sibling node, if it is a text node.
+    pub fn previous_text(&self) -> Option<StrTendril> {
+        self.prev_sibling().and_then(|node| node.text_contents())
+    }
+
     /// Returns the last sibling node of the selected node.
     #[inline]
     pub fn last_sibling(&self) -> Option<Self> {
@@ -234,6 +415,95 @@ impl NodeRef<'_> {
         TreeNodeOps::prepend_children_of(&mut nodes, &self.id, new_child_id);
     }
 
+    /// Appends copies of the nodes in the selection to the end of the selected node.
+    ///
+    /// Like [`Selection::append_selection`], the original nodes of the selection are removed from their parents,
+    /// and their copies (with descendants) are inserted. The selection may belong to another document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<ul id="list"><li>1</li></ul><div><li>2</li><li>3</li></div>"#);
+    /// let list_sel = doc.select("#list");
+    /// let list = list_sel.nodes().first().unwrap();
+    /// list.append_selection(&doc.select("div li"));
+    /// assert_eq!(list.inner_html().as_ref(), "<li>1</li><li>2</li><li>3</li>");
+    /// assert!(!doc.select("div li").exists());
+    /// ```
+    pub fn append_selection(&self, sel: &Selection) {
+        if sel.is_empty() {
+            return;
+        }
+        sel.remove();
+        self.tree
+            .copy_nodes_with_fn(sel.nodes(), |new_node_id| self.append_child(&new_node_id));
+    }
+
+    /// Prepends copies of the nodes in the selection to the beginning of the selected node,
+    /// keeping the order of the selection.
+    ///
+    /// Like [`Selection::prepend_selection`], the original nodes of the selection are removed from their parents,
+    /// and their copies (with descendants) are inserted. The selection may belong to another document.
+    pub fn prepend_selection(&self, sel: &Selection) {
+        if sel.is_empty() {
+            return;
+        }
+        sel.remove();
+        let sel_nodes: Vec<NodeRef> = sel.nodes().iter().rev().cloned().collect();
+        self.tree
+            .copy_nodes_with_fn(&sel_nodes, |new_node_id| self.prepend_child(&new_node_id));
+    }
+
+    /// Moves the selected node into `new_parent` at the given position,
+    /// detaching it from its current parent first.
+    ///
+    /// Nothing happens if `new_parent` belongs to another tree, if it is the selected node itself
+    /// or one of its descendants, or if the sibling given by [`InsertPosition::Before`]
+    /// or [`InsertPosition::After`] is not a child of `new_parent`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::{Document, InsertPosition};
+    ///
+    /// let doc = Document::from(r#"<ul id="list"><li id="a">A</li><li id="b">B</li></ul><p id="c">C</p>"#);
+    /// let list = doc.select_single("#list").nodes()[0].clone();
+    /// let item = doc.select_single("#c").nodes()[0].clone();
+    /// let b = doc.select_single("#b").nodes()[0].clone();
+    ///
+    /// item.move_to(&list, InsertPosition::Before(b));
+    /// assert_eq!(
+    ///     doc.select("body").html().as_ref(),
+    ///     r#"<body><ul id="list"><li id="a">A</li><p id="c">C</p><li id="b">B</li></ul></body>"#
+    /// );
+    /// ```
+    pub fn move_to(&self, new_parent: &NodeRef, position: InsertPosition) {
+        if !std::ptr::eq(self.tree, new_parent.tree)
+            || self.id == new_parent.id
+            || new_parent.ancestors_it(None).any(|node| node.id == self.id)
+        {
+            return;
+        }
+        match position {
+            InsertPosition::First => new_parent.prepend_child(self),
+            InsertPosition::Last => new_parent.append_child(self),
+            InsertPosition::Before(ref sibling) | InsertPosition::After(ref sibling) => {
+                if sibling.id == self.id
+                    || sibling.parent().map(|parent| parent.id) != Some(new_parent.id)
+                {
+                    return;
+                }
+                if matches!(position, InsertPosition::Before(_)) {
+                    sibling.insert_before(self);
+                } else {
+                    sibling.insert_after(self);
+                }
+            }
+        }
+    }
+
     /// Appends another node and it's siblings to the parent node
     /// of the selected node.
     #[inline]
@@ -265,6 +535,30 @@ impl NodeRef<'_> {
         TreeNodeOps::remove_from_parent(&mut nodes, &self.id);
     }
 
+    /// Replaces the current node with a new text node.
+    ///
+    /// The text is never interpreted as markup, so special characters (`<`, `&`)
+    /// are escaped when the node is serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p id="main">Price: <span class="price">10</span></p>"#);
+    /// let sel = doc.select(".price");
+    /// let span = sel.nodes().first().unwrap();
+    /// span.replace_with_text("<10 & 20>");
+    /// assert_eq!(doc.select("#main").inner_html().as_ref(), "Price: &lt;10 &amp; 20&gt;");
+    /// ```
+    pub fn replace_with_text<T>(&self, text: T)
+    where
+        T: Into<StrTendril>,
+    {
+        let text_node = self.tree.new_text(text);
+        self.replace_with(&text_node);
+    }
+
     /// Replaces the current node with other node, created from the given fragment html.
     /// Behaves similarly to [`crate::Selection::replace_with_html`] but only for one node.
     pub fn replace_with_html<T>(&self, html: T)
@@ -277,6 +571,35 @@ impl NodeRef<'_> {
         });
     }
 
+    /// Replaces the current node with its children, i.e. unwraps the node itself:
+    /// the children are moved to the node's position in its parent and the node is removed from the parent.
+    /// Does nothing if the node has no parent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p id="main">Hello, <span><b>World</b>!</span></p>"#);
+    /// let sel = doc.select("span");
+    /// let span = sel.nodes().first().unwrap();
+    /// span.replace_with_children();
+    /// assert_eq!(doc.select("#main").inner_html().as_ref(), "Hello, <b>World</b>!");
+    /// ```
+    pub fn replace_with_children(&self) {
+        let mut nodes = self.tree.nodes.borrow_mut();
+        let Some(node) = nodes.get(self.id.value) else {
+            return;
+        };
+        if node.parent.is_none() {
+            return;
+        }
+        if let Some(first_child_id) = node.first_child {
+            TreeNodeOps::insert_siblings_before(nodes.deref_mut(), &self.id, &first_child_id);
+        }
+        TreeNodeOps::remove_from_parent(&mut nodes, &self.id);
+    }
+
     /// Parses given fragment html and appends its contents to the selected node.
     pub fn append_html<T>(&self, html: T)
     where
@@ -317,6 +640,121 @@ impl NodeRef<'_> {
         });
     }
 
+    /// Parses given fragment html and wraps the selected node with it.
+    ///
+    /// The selected node is placed into the innermost first element of the fragment,
+    /// other top-level nodes of the fragment are ignored.
+    /// If the fragment contains no elements, the selected node remains unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"><img src="a.png"></div>"#);
+    /// let sel = doc.select("img");
+    /// let img = sel.nodes().first().unwrap();
+    /// img.wrap_html(r#"<figure><a href="a.png"></a></figure>"#);
+    /// assert!(doc.select("#main > figure > a > img").exists());
+    /// ```
+    pub fn wrap_html<T>(&self, html: T)
+    where
+        T: Into<StrTendril>,
+    {
+        self.merge_html_with_fn(html, |tree_nodes, new_node_id, node| {
+            TreeNodeOps::wrap_with(tree_nodes, &node.id, &new_node_id);
+        });
+    }
+
+    /// Wraps the selected node in a new element with the given name, if the node is inline:
+    /// a text node, or an element that is not a block (like `div` or `p`) or a structural one (like `td` or `script`).
+    /// It is useful to put loose inline content into block elements, like paragraphs.
+    ///
+    /// Returns `true` if the node was wrapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"><span>Loose</span><div>Block</div></div>"#);
+    /// for child in doc.select("#main").nodes()[0].children() {
+    ///     child.wrap_if_inline("p");
+    /// }
+    /// assert_eq!(
+    ///     doc.select("#main").inner_html().as_ref(),
+    ///     "<p><span>Loose</span></p><div>Block</div>"
+    /// );
+    /// ```
+    pub fn wrap_if_inline(&self, tag: &str) -> bool {
+        if !self.query_or(false, is_inline_node) {
+            return false;
+        }
+        let wrapper = self.tree.new_element(tag);
+        self.wrap_node(&wrapper);
+        true
+    }
+
+    /// Wraps the selected node with an existing element of the same tree, moving the element
+    /// to the place of the selected node.
+    ///
+    /// The selected node is placed into the innermost first element of the wrapper.
+    /// Nothing happens if the wrapper is not an element, belongs to another tree,
+    /// or is the selected node itself or one of its descendants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"><img src="a.png"></div>"#);
+    /// let sel = doc.select("img");
+    /// let img = sel.nodes().first().unwrap();
+    /// let figure = doc.tree.new_element("figure");
+    /// img.wrap_node(&figure);
+    /// assert!(doc.select("#main > figure > img").exists());
+    /// ```
+    pub fn wrap_node(&self, wrapper: &NodeRef) {
+        if !std::ptr::eq(self.tree, wrapper.tree)
+            || !wrapper.is_element()
+            || wrapper.id == self.id
+            || wrapper.ancestors_it(None).any(|node| node.id == self.id)
+        {
+            return;
+        }
+        let mut nodes = self.tree.nodes.borrow_mut();
+        TreeNodeOps::wrap_with(&mut nodes, &self.id, &wrapper.id);
+    }
+
+    /// Wraps each direct text child of the selected node, which is not whitespace-only,
+    /// in a new element with the given name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main">Loose <b>bold</b> text</div>"#);
+    /// let sel = doc.select("#main");
+    /// let main = sel.nodes().first().unwrap();
+    /// main.wrap_text_nodes("span");
+    /// assert_eq!(
+    ///     main.inner_html().as_ref(),
+    ///     "<span>Loose </span><b>bold</b><span> text</span>"
+    /// );
+    /// ```
+    pub fn wrap_text_nodes(&self, tag: &str) {
+        let text_nodes: Vec<Self> = self
+            .children_it(false)
+            .filter(|node| node.is_text() && !node.is_whitespace_only())
+            .collect();
+        for text_node in text_nodes {
+            let wrapper = self.tree.new_element(tag);
+            text_node.insert_before(&wrapper);
+            wrapper.append_child(&text_node);
+        }
+    }
+
     /// Parses given fragment html and sets its contents to the selected node.
     pub fn set_html<T>(&self, html: T)
     where
@@ -326,11 +764,63 @@ impl NodeRef<'_> {
         self.append_html(html);
     }
 
-    /// Parses given text and sets its contents to the selected node.
+    /// Replaces the children of the selected node with copies of the nodes in the selection.
+    ///
+    /// Like [`NodeRef::append_selection`], the original nodes of the selection are removed from their parents,
+    /// and their copies (with descendants) are inserted. The selection may belong to another document.
+    pub fn set_children_from_selection(&self, sel: &Selection) {
+        self.remove_children();
+        self.append_selection(sel);
+    }
+
+    /// Replaces the children of the selected node with the given content:
+    /// either an HTML string, which is parsed as with [`NodeRef::set_html`],
+    /// or a selection, which is handled as with [`NodeRef::set_children_from_selection`].
+    ///
+    /// # Examples
     ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"><p>Old</p></div><ul><li>1</li><li>2</li></ul>"#);
+    /// let sel = doc.select("#main");
+    /// let main = sel.nodes().first().unwrap();
+    ///
+    /// main.replace_children("<span>New</span>");
+    /// assert_eq!(main.inner_html().as_ref(), "<span>New</span>");
+    ///
+    /// main.replace_children(&doc.select("li"));
+    /// assert_eq!(main.inner_html().as_ref(), "<li>1</li><li>2</li>");
+    /// ```
+    pub fn replace_children<'b, T>(&self, content: T)
+    where
+        T: Into<NodeContent<'b>>,
+    {
+        match content.into() {
+            NodeContent::Html(html) => self.set_html(html),
+            NodeContent::Selection(sel) => self.set_children_from_selection(sel),
+        }
+    }
+
+    /// Sets the text contents of the selected node.
     ///
     /// This operation replaces any contents of the selected node with the given text.
-    /// Doesn't escapes the text.
+    /// The text is never interpreted as markup: it is stored in a single text node as is,
+    /// and special characters (`<`, `>`, `&`) are escaped when the node is serialized,
+    /// like the `innerText` setter in browsers. To insert markup, use [`NodeRef::set_html`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"><p>Old</p></div>"#);
+    /// let sel = doc.select("#main");
+    /// let main = sel.nodes().first().unwrap();
+    /// main.set_text("<b>bold?</b>");
+    /// assert_eq!(main.inner_html().as_ref(), "&lt;b&gt;bold?&lt;/b&gt;");
+    /// assert_eq!(main.text().as_ref(), "<b>bold?</b>");
+    /// ```
     pub fn set_text<T>(&self, text: T)
     where
         T: Into<StrTendril>,
@@ -383,6 +873,220 @@ impl NodeRef<'_> {
     pub fn element_children(&self) -> Vec<Self> {
         self.children_it(false).filter(|n| n.is_element()).collect()
     }
+
+    /// Returns the sibling elements of the selected node, excluding the node itself.
+    /// Returns an empty vector if the node has no parent.
+    pub fn siblings(&self) -> Vec<Self> {
+        let Some(parent) = self.parent() else {
+            return vec![];
+        };
+        parent
+            .children_it(false)
+            .filter(|n| n.id != self.id && n.is_element())
+            .collect()
+    }
+
+    /// Returns the zero-based position of the selected node among its element siblings.
+    /// Returns `0` for the first element child and for a node without a parent.
+    pub fn index(&self) -> usize {
+        self.index_of_kind(true)
+    }
+
+    /// Returns the zero-based position of the selected node among its siblings.
+    ///
+    /// # Arguments
+    ///
+    /// * `elements_only` - If `true`, only [`NodeData::Element`] siblings are counted,
+    ///   otherwise all siblings (including text and comment nodes) are counted.
+    pub fn index_of_kind(&self, elements_only: bool) -> usize {
+        let nodes = self.tree.nodes.borrow();
+        let mut index = 0;
+        if elements_only {
+            let mut current = self.id;
+            while let Some(id) = TreeNodeOps::prev_element_sibling_of(nodes.deref(), &current) {
+                index += 1;
+                current = id;
+            }
+        } else {
+            let mut prev = nodes.get(self.id.value).and_then(|n| n.prev_sibling);
+            while let Some(id) = prev {
+                index += 1;
+                prev = nodes.get(id.value).and_then(|n| n.prev_sibling);
+            }
+        }
+        index
+    }
+
+    /// Returns the number of child nodes of the selected node, including text and comment nodes.
+    pub fn child_count(&self) -> usize {
+        let nodes = self.tree.nodes.borrow();
+        child_nodes(Ref::clone(&nodes), &self.id, false).count()
+    }
+
+    /// Returns the number of children, that are [`NodeData::Element`]s of the selected node.
+    pub fn element_child_count(&self) -> usize {
+        let nodes = self.tree.nodes.borrow();
+        child_nodes(Ref::clone(&nodes), &self.id, false)
+            .filter(|id| nodes.get(id.value).map_or(false, |node| node.is_element()))
+            .count()
+    }
+
+    /// Returns the `n`-th (zero-based) child, that is an [`NodeData::Element`] of the selected node.
+    ///
+    /// It is a faster alternative to the `:nth-child` pseudo-class when only the position is known.
+    pub fn nth_element_child(&self, n: usize) -> Option<Self> {
+        let nodes = self.tree.nodes.borrow();
+        child_nodes(Ref::clone(&nodes), &self.id, false)
+            .filter(|id| nodes.get(id.value).map_or(false, |node| node.is_element()))
+            .nth(n)
+            .map(|id| NodeRef::new(id, self.tree))
+    }
+
+    /// Returns the `n`-th (zero-based) child element of the selected node, that has the given name.
+    ///
+    /// It is a faster alternative to the `:nth-of-type` pseudo-class when only the position is known.
+    pub fn nth_of_type(&self, name: &str, n: usize) -> Option<Self> {
+        let nodes = self.tree.nodes.borrow();
+        child_nodes(Ref::clone(&nodes), &self.id, false)
+            .filter(|id| {
+                nodes
+                    .get(id.value)
+                    .and_then(|node| node.as_element())
+                    .map_or(false, |el| el.name.local.as_ref() == name)
+            })
+            .nth(n)
+            .map(|id| NodeRef::new(id, self.tree))
+    }
+
+    /// Returns the closest `<table>` element: the selected node itself or its nearest ancestor.
+    pub fn closest_table(&self) -> Option<Self> {
+        if self.is_named_one_of(&["table"]) {
+            return Some(self.clone());
+        }
+        self.ancestors_it(None)
+            .find(|node| node.is_named_one_of(&["table"]))
+    }
+
+    /// Converts rows of the closest `<table>` element (see [`NodeRef::closest_table`]) into records,
+    /// mapping header cells to row cells.
+    ///
+    /// The header is the first row of the `<thead>` element, or the first row of the table if there is no `<thead>`.
+    /// Header names and cell values are trimmed texts of the cells. If a header cell is empty,
+    /// its zero-based column index is used as the name. Rows of nested tables are ignored,
+    /// cells beyond the header are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(
+    ///     r#"<table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>Alice</td><td>30</td></tr>
+    ///         <tr><td>Bob</td><td>25</td></tr>
+    ///     </table>"#,
+    /// );
+    /// let sel = doc.select("table");
+    /// let table = sel.nodes().first().unwrap();
+    /// let records = table.table_to_records();
+    /// assert_eq!(records.len(), 2);
+    /// assert_eq!(records[0]["Name"], "Alice".into());
+    /// assert_eq!(records[1]["Age"], "25".into());
+    /// ```
+    pub fn table_to_records(&self) -> Vec<HashMap<String, StrTendril>> {
+        let Some(table) = self.closest_table() else {
+            return vec![];
+        };
+
+        let mut header_row = None;
+        let mut rows = vec![];
+        for child in table.element_children() {
+            if child.is_named_one_of(&["tr"]) {
+                rows.push(child);
+            } else if child.is_named_one_of(&["thead"]) {
+                if header_row.is_none() {
+                    header_row = child
+                        .element_children()
+                        .into_iter()
+                        .find(|n| n.is_named_one_of(&["tr"]));
+                }
+            } else if child.is_named_one_of(&["tbody", "tfoot"]) {
+                rows.extend(
+                    child
+                        .element_children()
+                        .into_iter()
+                        .filter(|n| n.is_named_one_of(&["tr"])),
+                );
+            }
+        }
+        if header_row.is_none() && !rows.is_empty() {
+            header_row = Some(rows.remove(0));
+        }
+        let Some(header_row) = header_row else {
+            return vec![];
+        };
+
+        let row_cells = |row: &NodeRef| -> Vec<StrTendril> {
+            row.element_children()
+                .into_iter()
+                .filter(|n| n.is_named_one_of(&["td", "th"]))
+                .map(|cell| StrTendril::from(cell.text().trim()))
+                .collect()
+        };
+
+        let headers: Vec<String> = row_cells(&header_row)
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                if name.is_empty() {
+                    i.to_string()
+                } else {
+                    name.to_string()
+                }
+            })
+            .collect();
+
+        rows.iter()
+            .map(|row| headers.iter().cloned().zip(row_cells(row)).collect())
+            .collect()
+    }
+
+    fn text_contents(&self) -> Option<StrTendril> {
+        self.query_or(None, |node| match node.data {
+            NodeData::Text { ref contents } => Some(into_tendril(contents.clone())),
+            _ => None,
+        })
+    }
+
+    fn is_named_one_of(&self, names: &[&str]) -> bool {
+        self.query_or(false, |node| {
+            node.as_element()
+                .map_or(false, |el| names.contains(&el.name.local.as_ref()))
+        })
+    }
+
+    fn is_hidden_element(&self) -> bool {
+        if !self.is_element() {
+            return false;
+        }
+        if self.is_named_one_of(&["head", "script", "style", "template"]) || self.has_attr("hidden")
+        {
+            return true;
+        }
+        let Some(style) = self.attr("style") else {
+            return false;
+        };
+        style.split(';').any(|decl| {
+            let Some((prop, val)) = decl.split_once(':') else {
+                return false;
+            };
+            let val = val.trim().trim_end_matches("!important").trim_end();
+            let prop = prop.trim();
+            (prop.eq_ignore_ascii_case("display") && val.eq_ignore_ascii_case("none"))
+                || (prop.eq_ignore_ascii_case("visibility") && val.eq_ignore_ascii_case("hidden"))
+        })
+    }
 }
 
 impl NodeRef<'_> {
@@ -394,6 +1098,50 @@ impl NodeRef<'_> {
             .and_then(|node| node.as_element().map(|e| e.node_name()))
     }
 
+    /// Checks if the node is an element with the given local name. The comparison is exact.
+    pub fn has_name(&self, name: &str) -> bool {
+        self.query_or(false, |node| {
+            node.as_element()
+                .map_or(false, |el| el.name.local.as_ref() == name)
+        })
+    }
+
+    /// Checks if the node is an element with the given local name, ignoring ASCII case.
+    /// Useful for SVG and MathML elements with camel-cased names (e.g. `clipPath`).
+    pub fn has_name_ignore_ascii_case(&self, name: &str) -> bool {
+        self.query_or(false, |node| {
+            node.as_element().map_or(false, |el| {
+                el.name.local.as_ref().eq_ignore_ascii_case(name)
+            })
+        })
+    }
+
+    /// Returns the contents of the selected node if it is a [`NodeData::Comment`] otherwise `None`.
+    pub fn comment_text(&self) -> Option<StrTendril> {
+        self.query_or(None, |node| match node.data {
+            NodeData::Comment { ref contents } => Some(into_tendril(contents.clone())),
+            _ => None,
+        })
+    }
+
+    /// Returns the name of the selected node if it is a [`NodeData::Doctype`] otherwise `None`.
+    pub fn doctype_name(&self) -> Option<StrTendril> {
+        self.query_or(None, |node| match node.data {
+            NodeData::Doctype { ref name, .. } => Some(into_tendril(name.clone())),
+            _ => None,
+        })
+    }
+
+    /// Returns the target of the selected node if it is a [`NodeData::ProcessingInstruction`] otherwise `None`.
+    pub fn pi_target(&self) -> Option<StrTendril> {
+        self.query_or(None, |node| match node.data {
+            NodeData::ProcessingInstruction { ref target, .. } => {
+                Some(into_tendril(target.clone()))
+            }
+            _ => None,
+        })
+    }
+
     /// Checks if node has a specified class
     pub fn has_class(&self, class: &str) -> bool {
         self.query_or(false, |node| {
@@ -401,6 +1149,15 @@ impl NodeRef<'_> {
         })
     }
 
+    /// Checks if node has all the specified classes.
+    /// Entries containing whitespace are split into separate classes.
+    /// An empty slice returns `true` for any element; non-element nodes always return `false`.
+    pub fn has_classes(&self, classes: &[&str]) -> bool {
+        self.query_or(false, |node| {
+            node.as_element().map_or(false, |e| e.has_classes(classes))
+        })
+    }
+
     /// Adds a class to the node
     pub fn add_class(&self, class: &str) {
         self.update(|node| node.add_class(class));
@@ -411,6 +1168,18 @@ impl NodeRef<'_> {
         self.update(|node| node.remove_class(class));
     }
 
+    /// Replaces the class `old` with `new` if the node has `old`, keeping the order of other classes.
+    /// Does nothing if the node doesn't have `old`.
+    pub fn replace_class(&self, old: &str, new: &str) {
+        self.update(|node| node.replace_class(old, new));
+    }
+
+    /// Toggles a class on the node: removes it if present, adds it otherwise.
+    /// Multiple class names can be specified, separated by whitespace.
+    pub fn toggle_class(&self, class: &str) {
+        self.update(|node| node.toggle_class(class));
+    }
+
     /// Returns the value of the specified attribute
     pub fn attr(&self, name: &str) -> Option<StrTendril> {
         self.query_or(None, |node| node.as_element().and_then(|e| e.attr(name)))
@@ -425,6 +1194,81 @@ impl NodeRef<'_> {
             .unwrap_or_else(|| StrTendril::from(default))
     }
 
+    /// Returns the value of the specified attribute parsed into `T`.
+    /// Leading and trailing whitespace is ignored.
+    /// Returns `None` if the attribute is missing or its value fails to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div data-ratio="1.5" data-name="x">Text</div>"#);
+    /// let node = doc.select("div").nodes()[0].clone();
+    /// assert_eq!(node.attr_as::<f64>("data-ratio"), Some(1.5));
+    /// assert_eq!(node.attr_as::<f64>("data-name"), None);
+    /// assert_eq!(node.attr_as::<f64>("data-missing"), None);
+    /// ```
+    pub fn attr_as<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.attr(name).and_then(|v| v.trim().parse().ok())
+    }
+
+    /// Returns the value of the specified attribute as a boolean.
+    ///
+    /// Following HTML boolean attribute rules, the attribute is `true` when it is present,
+    /// whatever its value is (even `disabled="false"`), and `false` when it is missing.
+    pub fn attr_as_bool(&self, name: &str) -> bool {
+        self.has_attr(name)
+    }
+
+    /// Returns the value of the specified attribute parsed as an integer.
+    /// Returns `None` if the attribute is missing or its value is not an integer.
+    pub fn attr_as_int(&self, name: &str) -> Option<i64> {
+        self.attr_as(name)
+    }
+
+    /// Returns the value of the specified attribute from the node itself or its nearest ancestor having it.
+    ///
+    /// This mirrors how inherited attributes, like `lang` or `dir`, work in HTML.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div dir="rtl"><p><span id="target">text</span></p></div>"#);
+    /// let sel = doc.select("#target");
+    /// let span = sel.nodes().first().unwrap();
+    /// assert_eq!(span.attr_or_default_from_ancestors("dir"), Some("rtl".into()));
+    /// assert_eq!(span.attr_or_default_from_ancestors("lang"), None);
+    /// ```
+    pub fn attr_or_default_from_ancestors(&self, name: &str) -> Option<StrTendril> {
+        self.attr(name)
+            .or_else(|| self.ancestors_it(None).find_map(|node| node.attr(name)))
+    }
+
+    /// Returns the text direction of the node, resolved from the `dir` attribute of the node itself
+    /// or its nearest ancestor having it. If there is no such attribute, or its value is not
+    /// `rtl` or `auto`, the direction is [`Dir::Ltr`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::{Dir, Document};
+    ///
+    /// let doc = Document::from(r#"<div dir="rtl"><p><span id="target">مرحبا</span></p></div>"#);
+    /// let sel = doc.select("#target");
+    /// let span = sel.nodes().first().unwrap();
+    /// assert_eq!(span.text_direction(), Dir::Rtl);
+    /// ```
+    pub fn text_direction(&self) -> Dir {
+        match self.attr_or_default_from_ancestors("dir") {
+            Some(dir) if dir.eq_ignore_ascii_case("rtl") => Dir::Rtl,
+            Some(dir) if dir.eq_ignore_ascii_case("auto") => Dir::Auto,
+            _ => Dir::Ltr,
+        }
+    }
+
     /// Returns all attributes
     pub fn attrs(&self) -> Vec<Attribute> {
         self.query_or(vec![], |node| {
@@ -437,6 +1281,12 @@ impl NodeRef<'_> {
         self.update(|node| node.set_attr(name, val));
     }
 
+    /// Sets the values of the specified attributes to the node, borrowing it only once.
+    /// Existing values are overwritten; if a name occurs more than once, the last value wins.
+    pub fn set_attrs(&self, attrs: &[(&str, &str)]) {
+        self.update(|node| node.set_attrs(attrs));
+    }
+
     /// Removes the specified attribute from the element.
     pub fn remove_attr(&self, name: &str) {
         self.update(|node| node.remove_attr(name));
@@ -463,6 +1313,35 @@ impl NodeRef<'_> {
     pub fn rename(&self, name: &str) {
         self.update(|node| node.rename(name));
     }
+
+    /// Renames the node and replaces all of its attributes with the given ones in a single step,
+    /// if node is an [`NodeData::Element`]. The children of the node are preserved.
+    ///
+    /// Use [`NodeRef::rename`] to keep the attributes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p><font color="red">Warning</font></p>"#);
+    /// let sel = doc.select("font");
+    /// let font = sel.nodes().first().unwrap();
+    /// font.replace_tag("span", &[("style", "color:red")]);
+    /// assert_eq!(doc.select("p").inner_html().as_ref(), r#"<span style="color:red">Warning</span>"#);
+    /// ```
+    pub fn replace_tag(&self, name: &str, attrs: &[(&str, &str)]) {
+        self.update(|node| {
+            if !node.is_element() {
+                return;
+            }
+            node.rename(name);
+            node.remove_all_attrs();
+            for (attr_name, attr_val) in attrs {
+                node.set_attr(attr_name, attr_val);
+            }
+        });
+    }
 }
 
 impl NodeRef<'_> {
@@ -485,6 +1364,37 @@ impl NodeRef<'_> {
     pub fn is_text(&self) -> bool {
         self.query_or(false, |node| node.is_text())
     }
+
+    /// Returns true if this node is a text node that contains only whitespace characters.
+    pub fn is_whitespace_only(&self) -> bool {
+        self.query_or(false, |node| match node.data {
+            NodeData::Text { ref contents } => contents.chars().all(char::is_whitespace),
+            _ => false,
+        })
+    }
+
+    /// Checks if the node is likely to be visible to the user.
+    ///
+    /// Returns `false` if the node or any of its ancestors is a non-rendered element
+    /// (`head`, `script`, `style`, `template`), has the `hidden` attribute,
+    /// or has `display: none` or `visibility: hidden` in its inline `style` attribute.
+    ///
+    /// This is only a heuristic: stylesheets and the CSS cascade are not taken into account.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div style="display: none"><p id="hidden">Hidden</p></div><p id="shown">Shown</p>"#);
+    /// assert!(!doc.select("#hidden").nodes()[0].is_visible());
+    /// assert!(doc.select("#shown").nodes()[0].is_visible());
+    /// ```
+    pub fn is_visible(&self) -> bool {
+        !std::iter::once(self.clone())
+            .chain(self.ancestors_it(None))
+            .any(|node| node.is_hidden_element())
+    }
     /// Returns true if this node is a comment.
     pub fn is_comment(&self) -> bool {
         self.query_or(false, |node| node.is_comment())
@@ -524,20 +1434,76 @@ impl NodeRef<'_> {
         self.serialize_html(TraversalScope::ChildrenOnly(None))
     }
 
+    /// Returns the indented HTML representation of the node and its descendants,
+    /// which is convenient for inspecting the structure of an element.
+    ///
+    /// Every element, text, and comment is written on a new line, indented by `indent` spaces per nesting level.
+    /// Whitespace-only text nodes are skipped and other text nodes are trimmed,
+    /// so the result is not equivalent to the original HTML in rendering.
+    /// Contents of `pre`, `textarea`, `script` and `style` elements are kept as is.
+    /// Returns an empty string if serialization fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"><ul><li>One</li><li><br></li></ul></div>"#);
+    /// let sel = doc.select("#main");
+    /// let main = sel.nodes().first().unwrap();
+    /// assert_eq!(
+    ///     main.outer_html_pretty(2).as_ref(),
+    ///     "<div id=\"main\">\n  <ul>\n    <li>\n      One\n    </li>\n    <li>\n      <br>\n    </li>\n  </ul>\n</div>"
+    /// );
+    /// ```
+    pub fn outer_html_pretty(&self, indent: usize) -> StrTendril {
+        let inner = PrettySerializableNodeRef::new(self.clone(), indent);
+        serialize_with_scope(&inner, TraversalScope::IncludeNode).unwrap_or_default()
+    }
+
+    /// Writes the HTML representation of the node and its descendants to the writer,
+    /// without buffering the whole result in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"><p>Text</p></div>"#);
+    /// let sel = doc.select("#main");
+    /// let main = sel.nodes().first().unwrap();
+    /// let mut buf = vec![];
+    /// main.serialize_to(&mut buf).unwrap();
+    /// assert_eq!(buf, br#"<div id="main"><p>Text</p></div>"#);
+    /// ```
+    pub fn serialize_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let inner: SerializableNodeRef = self.clone().into();
+        write_with_scope(writer, &inner, TraversalScope::IncludeNode)
+    }
+
+    /// Writes the HTML representation of the node's descendants (without the node itself) to the writer,
+    /// without buffering the whole result in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"><p>Text</p></div>"#);
+    /// let sel = doc.select("#main");
+    /// let main = sel.nodes().first().unwrap();
+    /// let mut buf = vec![];
+    /// main.serialize_children_to(&mut buf).unwrap();
+    /// assert_eq!(buf, b"<p>Text</p>");
+    /// ```
+    pub fn serialize_children_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        let inner: SerializableNodeRef = self.clone().into();
+        write_with_scope(writer, &inner, TraversalScope::ChildrenOnly(None))
+    }
+
     fn serialize_html(&self, traversal_scope: TraversalScope) -> Option<StrTendril> {
         let inner: SerializableNodeRef = self.clone().into();
-        let mut result = vec![];
-        serialize(
-            &mut result,
-            &inner,
-            SerializeOpts {
-                scripting_enabled: false,
-                create_missing_parent: false,
-                traversal_scope,
-            },
-        )
-        .ok()?;
-        StrTendril::try_from_byte_slice(&result).ok()
+        serialize_with_scope(&inner, traversal_scope)
     }
 
     /// Returns the text of the node and its descendants.
@@ -546,12 +1512,135 @@ impl NodeRef<'_> {
         TreeNodeOps::text_of(nodes, self.id)
     }
 
+    /// Returns the text of the node and its descendants, like [`NodeRef::text`],
+    /// but the `alt` attribute of each `img` element is included in place of the image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p>I <img src="heart.png" alt="love"> Rust</p>"#);
+    /// let sel = doc.select("p");
+    /// let p = sel.nodes().first().unwrap();
+    /// assert_eq!(p.text().as_ref(), "I  Rust");
+    /// assert_eq!(p.text_with_alt().as_ref(), "I love Rust");
+    /// ```
+    pub fn text_with_alt(&self) -> StrTendril {
+        let nodes = self.tree.nodes.borrow();
+        let mut ops = vec![self.id];
+        let mut text = StrTendril::new();
+
+        while let Some(id) = ops.pop() {
+            let Some(node) = nodes.get(id.value) else {
+                continue;
+            };
+            match node.data {
+                NodeData::Element(ref el) if el.name.local == local_name!("img") => {
+                    if let Some(alt) = el.attr("alt") {
+                        text.push_tendril(&alt);
+                    }
+                }
+                NodeData::Document | NodeData::Fragment | NodeData::Element(_) => {
+                    ops.extend(child_nodes(Ref::clone(&nodes), &id, true));
+                }
+                NodeData::Text { ref contents } => text.push_slice(contents),
+                _ => continue,
+            }
+        }
+        text
+    }
+
+    /// Returns the text of the node and its descendants with HTML special characters
+    /// (`&`, `<`, `>`, `"`, `'`) escaped, so it can be safely embedded into HTML, including attribute values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p>Tom &amp; "Jerry" &lt;3</p>"#);
+    /// let sel = doc.select("p");
+    /// let p = sel.nodes().first().unwrap();
+    /// assert_eq!(p.text().as_ref(), r#"Tom & "Jerry" <3"#);
+    /// assert_eq!(p.html_escaped_text().as_ref(), "Tom &amp; &quot;Jerry&quot; &lt;3");
+    /// ```
+    pub fn html_escaped_text(&self) -> StrTendril {
+        escape_html(&self.text())
+    }
+
     /// Returns the text of the node without its descendants.
     pub fn immediate_text(&self) -> StrTendril {
         let nodes = self.tree.nodes.borrow();
         TreeNodeOps::immediate_text_of(nodes, self.id)
     }
 
+    /// Returns the text of the node and its descendants, formatted similarly to
+    /// the `innerText` property in browsers.
+    ///
+    /// Whitespace is collapsed, block elements are separated by a blank line, `li`, `tr`, `dt`, `dd` are
+    /// placed on their own lines, `br` produces a line break, contents of `pre` elements are kept as is,
+    /// `script`/`style` contents are skipped. The result is trimmed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<div>  First   line<br>Second line<p>Paragraph</p><script>let x;</script></div>");
+    /// let sel = doc.select("div");
+    /// let div = sel.nodes().first().unwrap();
+    /// assert_eq!(div.inner_text().as_ref(), "First line\nSecond line\n\nParagraph");
+    /// ```
+    pub fn inner_text(&self) -> StrTendril {
+        let nodes = self.tree.nodes.borrow();
+        format_text(nodes, self.id, None)
+    }
+
+    /// Returns the formatted text of the node and its descendants,
+    /// where every link is followed by its target: `click here (https://example.com)`.
+    ///
+    /// The text is formatted similarly to how a browser renders it:
+    /// whitespace is collapsed, block elements are separated by a blank line,
+    /// `br` produces a line break and `script`/`style` contents are skipped.
+    /// It is a lighter alternative to a full Markdown serialization.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p>Please, <a href="https://example.com">click here</a>.</p>"#);
+    /// let text = doc.root().serialize_text_with_links();
+    /// assert_eq!(text.as_ref(), "Please, click here (https://example.com).");
+    /// ```
+    pub fn serialize_text_with_links(&self) -> StrTendril {
+        self.serialize_text_with_links_fn(|href| format!(" ({})", href))
+    }
+
+    /// Returns the formatted text of the node and its descendants, like [`NodeRef::serialize_text_with_links`],
+    /// but the link annotation is produced by the given function.
+    ///
+    /// The function receives the value of the `href` attribute of each link,
+    /// and its result is appended right after the link's text. Links with an empty `href` are not annotated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p>Please, <a href="/next">click here</a>.</p>"#);
+    /// let text = doc.root().serialize_text_with_links_fn(|href| format!(" <{}>", href));
+    /// assert_eq!(text.as_ref(), "Please, click here </next>.");
+    /// ```
+    pub fn serialize_text_with_links_fn<F>(&self, f: F) -> StrTendril
+    where
+        F: Fn(&str) -> String,
+    {
+        let nodes = self.tree.nodes.borrow();
+        format_text(nodes, self.id, Some(&f))
+    }
+
     /// Checks if the node contains the specified text
     pub fn has_text(&self, needle: &str) -> bool {
         let mut ops = vec![self.id];
@@ -644,6 +1733,37 @@ impl NodeRef<'_> {
         }
     }
 
+    /// Tidies up the node before serialization.
+    ///
+    /// It merges adjacent text nodes (like [`NodeRef::normalize`]) and removes whitespace-only text nodes,
+    /// that are used only for formatting between block elements, so the serialized html becomes compact.
+    /// Whitespace between inline elements and inside `pre` or `textarea` elements is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<div id=\"main\">\n  <p><b>Hello</b> <i>World</i></p>\n  <p>!</p>\n</div>");
+    /// let sel = doc.select("#main");
+    /// let main = sel.nodes().first().unwrap();
+    /// main.html_tidy();
+    /// assert_eq!(main.inner_html().as_ref(), "<p><b>Hello</b> <i>World</i></p><p>!</p>");
+    /// ```
+    pub fn html_tidy(&self) {
+        self.normalize();
+        let formatting_ids: Vec<NodeId> = {
+            let nodes = self.tree.nodes.borrow();
+            descendant_nodes(Ref::clone(&nodes), &self.id)
+                .filter(|id| is_formatting_whitespace(&nodes, id))
+                .collect()
+        };
+        let mut nodes = self.tree.nodes.borrow_mut();
+        for id in formatting_ids {
+            TreeNodeOps::remove_from_parent(nodes.deref_mut(), &id);
+        }
+    }
+
     /// Checks if the node matches the given matcher
     pub fn is_match(&self, matcher: &Matcher) -> bool {
         self.is_element() && matcher.match_element(self)
@@ -654,6 +1774,66 @@ impl NodeRef<'_> {
         Matcher::new(sel).map_or(false, |matcher| self.is_match(&matcher))
     }
 
+    /// Returns the closest element matching the given matcher: the node itself or its nearest ancestor.
+    /// Non-element nodes (like the document root) are never returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::{Document, Matcher};
+    ///
+    /// let doc = Document::from(r#"<div class="card"><p><b id="target">Text</b></p></div>"#);
+    /// let sel = doc.select("#target");
+    /// let node = sel.nodes().first().unwrap();
+    /// let card = node.closest(&Matcher::new(".card").unwrap()).unwrap();
+    /// assert_eq!(card.node_name().unwrap().as_ref(), "div");
+    /// ```
+    pub fn closest(&self, matcher: &Matcher) -> Option<Self> {
+        let mut caches = Default::default();
+        std::iter::once(self.clone())
+            .chain(self.ancestors_it(None))
+            .find(|node| node.is_element() && matcher.match_element_with_caches(node, &mut caches))
+    }
+
+    /// Checks if any direct child element of the node matches the given selector,
+    /// like the `:has(> sel)` pseudo-class. Unlike `:has(sel)`, deeper descendants are not tested.
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="main"><section><p class="x">1</p></section></div>"#);
+    /// let sel = doc.select("#main");
+    /// let main = sel.nodes().first().unwrap();
+    /// assert!(main.has_child_matching("section"));
+    /// assert!(!main.has_child_matching("p.x"));
+    /// ```
+    pub fn has_child_matching(&self, sel: &str) -> bool {
+        let matcher = Matcher::new(sel).expect("Invalid CSS selector");
+        self.has_child_matching_matcher(&matcher)
+    }
+
+    /// Checks if any direct child element of the node matches the given selector.
+    /// Returns `None` if failed to parse the selector.
+    pub fn try_has_child_matching(&self, sel: &str) -> Option<bool> {
+        Matcher::new(sel)
+            .ok()
+            .map(|matcher| self.has_child_matching_matcher(&matcher))
+    }
+
+    /// Checks if any direct child element of the node matches the given matcher.
+    pub fn has_child_matching_matcher(&self, matcher: &Matcher) -> bool {
+        let mut caches = Default::default();
+        self.children_it(false).any(|child| {
+            child.is_element() && matcher.match_element_with_caches(&child, &mut caches)
+        })
+    }
+
     /// Returns the base URI of the document.
     ///
     /// This is the value of the `<base>` element in the document's head, or `None` if the document does not have a `<base>` element.
@@ -663,13 +1843,26 @@ impl NodeRef<'_> {
 
     /// Finds all descendant elements of this node that match the given path.
     ///
-    /// The path is a sequence of element names. The method returns a vector of
+    /// The path is a sequence of steps. Each step is an element name (`"div"`) or a wildcard (`"*"`),
+    /// optionally followed by an attribute predicate: `"a[@href]"` matches only `a` elements
+    /// having the `href` attribute. The method returns a vector of
     /// [`NodeRef`]s that correspond to the matching elements. The elements are
     /// returned in the order they appear in the document tree.
     ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div><p><a href="/one">One</a><a>Two</a></p></div>"#);
+    /// let links = doc.root().find(&["div", "*", "a[@href]"]);
+    /// assert_eq!(links.len(), 1);
+    /// assert_eq!(links[0].text().as_ref(), "One");
+    /// ```
+    ///
     /// # Experimental
     /// This method is experimental and may change in the future. The `path` argument will be revised.
-    pub fn find(&self, path: &[&str]) -> Vec<NodeRef> {
+    pub fn find(&self, path: &[&str]) -> Vec<Self> {
         let nodes = self.tree.nodes.borrow();
         let found_ids = Traversal::find_descendant_elements(&nodes, self.id, path);
         found_ids
@@ -678,3 +1871,28 @@ impl NodeRef<'_> {
             .collect()
     }
 }
+
+fn serialize_with_scope<T: Serialize>(
+    inner: &T,
+    traversal_scope: TraversalScope,
+) -> Option<StrTendril> {
+    let mut result = vec![];
+    write_with_scope(&mut result, inner, traversal_scope).ok()?;
+    StrTendril::try_from_byte_slice(&result).ok()
+}
+
+fn write_with_scope<W: io::Write, T: Serialize>(
+    writer: W,
+    inner: &T,
+    traversal_scope: TraversalScope,
+) -> io::Result<()> {
+    serialize(
+        writer,
+        inner,
+        SerializeOpts {
+            scripting_enabled: false,
+            create_missing_parent: false,
+            traversal_scope,
+        },
+    )
+}
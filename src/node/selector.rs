@@ -1,7 +1,9 @@
 use std::ops::Deref;
 
-use html5ever::{local_name, namespace_url, ns};
+use html5ever::{local_name, namespace_url, ns, LocalName};
+use precomputed_hash::PrecomputedHash;
 use selectors::attr::{AttrSelectorOperation, CaseSensitivity, NamespaceConstraint};
+use selectors::bloom::BloomFilter;
 use selectors::context::MatchingContext;
 use selectors::matching::ElementSelectorFlags;
 use selectors::parser::SelectorImpl;
@@ -15,8 +17,28 @@ use crate::matcher::{InnerSelector, NonTSPseudoClass};
 impl selectors::Element for NodeRef<'_> {
     type Impl = InnerSelector;
 
-    fn add_element_unique_hashes(&self, _filter: &mut selectors::bloom::BloomFilter) -> bool {
-        false
+    /// Adds hashes of the element's local name, namespace, id, classes and attribute names to the filter.
+    /// The filter allows the `:has()` pseudo-class to reject a subtree without scanning it again,
+    /// so the hashes must cover everything `selectors` collects from a compound selector.
+    fn add_element_unique_hashes(&self, filter: &mut BloomFilter) -> bool {
+        self.query_or(false, |node| {
+            let Some(el) = node.as_element() else {
+                return false;
+            };
+            filter.insert_hash(el.name.local.precomputed_hash());
+            filter.insert_hash(el.name.ns.precomputed_hash());
+            for attr in el.attrs.iter() {
+                filter.insert_hash(attr.name.local.precomputed_hash());
+                if attr.name.local == local_name!("id") {
+                    filter.insert_hash(LocalName::from(attr.value.as_ref()).precomputed_hash());
+                } else if attr.name.local == local_name!("class") {
+                    for class in attr.value.split_whitespace() {
+                        filter.insert_hash(LocalName::from(class).precomputed_hash());
+                    }
+                }
+            }
+            true
+        })
     }
 
     fn has_custom_state(&self, _name: &<Self::Impl as SelectorImpl>::Identifier) -> bool {
@@ -138,9 +160,12 @@ impl selectors::Element for NodeRef<'_> {
         use self::NonTSPseudoClass::*;
         // TODO: this also can be "optimized", but it's not worth it
         match pseudo {
-            Active | Focus | Hover | Enabled | Disabled | Checked | Indeterminate | Visited => {
-                false
-            }
+            Active | Focus | Hover | Indeterminate | Visited => false,
+            Enabled => is_form_control(self) && !is_disabled(self),
+            Disabled => is_form_control(self) && is_disabled(self),
+            Checked => is_checked(self),
+            Required => is_requirable(self) && self.has_attr("required"),
+            Optional => is_requirable(self) && !self.has_attr("required"),
             AnyLink | Link => match self.node_name() {
                 Some(node_name) => {
                     matches!(node_name.deref(), "a" | "area" | "link") && self.has_attr("href")
@@ -236,3 +261,58 @@ impl selectors::Element for NodeRef<'_> {
 
     fn apply_selector_flags(&self, _flags: ElementSelectorFlags) {}
 }
+
+/// Checks if the element is a form control, which can be enabled or disabled.
+fn is_form_control(node: &NodeRef) -> bool {
+    node.node_name().map_or(false, |name| {
+        matches!(
+            name.as_ref(),
+            "button" | "input" | "select" | "textarea" | "optgroup" | "option" | "fieldset"
+        )
+    })
+}
+
+/// Checks if the form control is disabled by its own `disabled` attribute,
+/// by a disabled `optgroup` (for options), or by a disabled ancestor `fieldset`.
+fn is_disabled(node: &NodeRef) -> bool {
+    if node.has_attr("disabled") {
+        return true;
+    }
+    let is_option = node
+        .node_name()
+        .map_or(false, |name| name.as_ref() == "option");
+    node.ancestors_it(None).any(|ancestor| {
+        let Some(name) = ancestor.node_name() else {
+            return false;
+        };
+        match name.as_ref() {
+            "fieldset" => ancestor.has_attr("disabled"),
+            "optgroup" => is_option && ancestor.has_attr("disabled"),
+            _ => false,
+        }
+    })
+}
+
+/// Checks if the element is a checked checkbox or radio button, or a selected option.
+fn is_checked(node: &NodeRef) -> bool {
+    let Some(name) = node.node_name() else {
+        return false;
+    };
+    match name.as_ref() {
+        "input" => {
+            node.has_attr("checked")
+                && node.attr("type").map_or(false, |t| {
+                    t.eq_ignore_ascii_case("checkbox") || t.eq_ignore_ascii_case("radio")
+                })
+        }
+        "option" => node.has_attr("selected"),
+        _ => false,
+    }
+}
+
+/// Checks if the element can be matched by `:required` and `:optional`.
+fn is_requirable(node: &NodeRef) -> bool {
+    node.node_name().map_or(false, |name| {
+        matches!(name.as_ref(), "input" | "select" | "textarea")
+    })
+}
@@ -9,6 +9,9 @@ use super::node_data::NodeData;
 use super::node_ref::NodeRef;
 use super::{child_nodes, NodeId};
 
+/// Elements whose contents are serialized as is by [`PrettySerializableNodeRef`].
+static VERBATIM_ELEMENTS: &[&str] = &["pre", "textarea", "listing", "plaintext", "script", "style"];
+
 enum SerializeOp<'a> {
     Open(NodeId),
     Close(&'a QualName),
@@ -82,3 +85,136 @@ impl Serialize for SerializableNodeRef<'_> {
         Ok(())
     }
 }
+
+enum PrettySerializeOp<'a> {
+    Open(NodeId, usize),
+    /// Closes the element. If the depth is set, the closing tag is written on a new line.
+    Close(&'a QualName, Option<usize>),
+}
+
+/// Serializable wrapper of Node, which writes every node on a new line, indented according to its depth.
+///
+/// Whitespace-only text nodes are skipped, and other text nodes are trimmed.
+/// Contents of preformatted elements, scripts and styles are written as is.
+pub(crate) struct PrettySerializableNodeRef<'a> {
+    node: NodeRef<'a>,
+    indent: usize,
+}
+
+impl<'a> PrettySerializableNodeRef<'a> {
+    pub(crate) fn new(node: NodeRef<'a>, indent: usize) -> Self {
+        Self { node, indent }
+    }
+}
+
+impl Serialize for PrettySerializableNodeRef<'_> {
+    fn serialize<S>(&self, serializer: &mut S, traversal_scope: TraversalScope) -> io::Result<()>
+    where
+        S: Serializer,
+    {
+        let nodes = self.node.tree.nodes.borrow();
+        let id = self.node.id;
+        let mut is_first_line = true;
+        let mut new_line = |serializer: &mut S, depth: usize| -> io::Result<()> {
+            if is_first_line {
+                is_first_line = false;
+                return Ok(());
+            }
+            let mut line_start = String::with_capacity(1 + depth * self.indent);
+            line_start.push('\n');
+            line_start.extend(std::iter::repeat(' ').take(depth * self.indent));
+            serializer.write_text(&line_start)
+        };
+        let is_blank = |id: &NodeId| match nodes.get(id.value).map(|node| &node.data) {
+            Some(NodeData::Text { contents }) => contents.trim().is_empty(),
+            _ => false,
+        };
+
+        let mut ops = match traversal_scope {
+            TraversalScope::IncludeNode => vec![PrettySerializeOp::Open(id, 0)],
+            TraversalScope::ChildrenOnly(_) => child_nodes(Ref::clone(&nodes), &id, true)
+                .map(|id| PrettySerializeOp::Open(id, 0))
+                .collect(),
+        };
+        while let Some(op) = ops.pop() {
+            match op {
+                PrettySerializeOp::Open(id, depth) => {
+                    let node = match nodes.get(id.value) {
+                        Some(node) => node,
+                        None => continue,
+                    };
+
+                    match &node.data {
+                        NodeData::Element(e) => {
+                            new_line(serializer, depth)?;
+                            serializer.start_elem(
+                                e.name.clone(),
+                                e.attrs.iter().map(|at| (&at.name, &at.value[..])),
+                            )?;
+
+                            if VERBATIM_ELEMENTS.contains(&e.name.local.as_ref()) {
+                                SerializableNodeRef::from(NodeRef::new(id, self.node.tree))
+                                    .serialize(serializer, TraversalScope::ChildrenOnly(None))?;
+                                ops.push(PrettySerializeOp::Close(&e.name, None));
+                                continue;
+                            }
+
+                            let children: Vec<NodeId> = child_nodes(Ref::clone(&nodes), &id, true)
+                                .filter(|id| !is_blank(id))
+                                .collect();
+                            if children.is_empty() {
+                                ops.push(PrettySerializeOp::Close(&e.name, None));
+                            } else {
+                                ops.push(PrettySerializeOp::Close(&e.name, Some(depth)));
+                                ops.extend(
+                                    children
+                                        .into_iter()
+                                        .map(|id| PrettySerializeOp::Open(id, depth + 1)),
+                                );
+                            }
+                            Ok(())
+                        }
+                        NodeData::Text { ref contents } => {
+                            let text = contents.trim();
+                            if text.is_empty() {
+                                continue;
+                            }
+                            new_line(serializer, depth)?;
+                            serializer.write_text(text)
+                        }
+                        NodeData::Doctype { ref name, .. } => {
+                            new_line(serializer, depth)?;
+                            serializer.write_doctype(name)
+                        }
+                        NodeData::Comment { ref contents } => {
+                            new_line(serializer, depth)?;
+                            serializer.write_comment(contents)
+                        }
+                        NodeData::ProcessingInstruction {
+                            ref target,
+                            ref contents,
+                        } => {
+                            new_line(serializer, depth)?;
+                            serializer.write_processing_instruction(target, contents)
+                        }
+                        NodeData::Document | NodeData::Fragment => {
+                            ops.extend(
+                                child_nodes(Ref::clone(&nodes), &id, true)
+                                    .map(|id| PrettySerializeOp::Open(id, depth)),
+                            );
+                            continue;
+                        }
+                    }?;
+                }
+                PrettySerializeOp::Close(name, depth) => {
+                    if let Some(depth) = depth {
+                        new_line(serializer, depth)?;
+                    }
+                    serializer.end_elem(name.clone())?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,333 @@
+use std::cell::Ref;
+
+use tendril::StrTendril;
+
+use super::child_nodes;
+use super::inner::TreeNode;
+use super::node_data::NodeData;
+use super::NodeId;
+
+/// Elements that are separated from the surrounding text by a blank line.
+static BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Elements that are placed on their own line.
+static LINE_ELEMENTS: &[&str] = &["dd", "dt", "hr", "li", "tr"];
+
+/// Elements whose contents are never rendered as text.
+static SKIP_ELEMENTS: &[&str] = &["script", "style", "noscript"];
+
+/// Elements that are not rendered inline, besides [`BLOCK_ELEMENTS`] and [`LINE_ELEMENTS`].
+static STRUCTURAL_ELEMENTS: &[&str] = &[
+    "base", "body", "caption", "col", "colgroup", "head", "html", "link", "meta", "noscript",
+    "script", "style", "tbody", "td", "template", "tfoot", "th", "thead", "title",
+];
+
+/// Elements whose whitespace is significant.
+static PREFORMATTED_ELEMENTS: &[&str] = &["pre", "textarea", "listing", "plaintext"];
+
+fn is_block_node(node: &TreeNode) -> bool {
+    match node.data {
+        NodeData::Document | NodeData::Fragment => true,
+        NodeData::Element(ref el) => {
+            let name = el.name.local.as_ref();
+            BLOCK_ELEMENTS.contains(&name)
+                || LINE_ELEMENTS.contains(&name)
+                || STRUCTURAL_ELEMENTS.contains(&name)
+        }
+        _ => false,
+    }
+}
+
+/// Checks if the node is an inline node: a text node, or an element that is rendered inline.
+pub(crate) fn is_inline_node(node: &TreeNode) -> bool {
+    match node.data {
+        NodeData::Text { .. } => true,
+        NodeData::Element(_) => !is_block_node(node),
+        _ => false,
+    }
+}
+
+/// Checks if the node is a whitespace-only text node, which is used only for formatting:
+/// each of its sides is either a block element, or a boundary of a block parent.
+/// Whitespace inside preformatted elements (`pre`, `textarea`) is never a formatting one.
+pub(crate) fn is_formatting_whitespace(nodes: &[TreeNode], id: &NodeId) -> bool {
+    let Some(node) = nodes.get(id.value) else {
+        return false;
+    };
+    let NodeData::Text { ref contents } = node.data else {
+        return false;
+    };
+    if !contents.chars().all(char::is_whitespace) {
+        return false;
+    }
+    let Some(parent) = node.parent.and_then(|id| nodes.get(id.value)) else {
+        return false;
+    };
+    if is_preformatted(nodes, id) {
+        return false;
+    }
+
+    let is_block_side = |sibling_id: Option<NodeId>| match sibling_id {
+        Some(sibling_id) => nodes.get(sibling_id.value).map_or(false, is_block_node),
+        None => is_block_node(parent),
+    };
+    is_block_side(node.prev_sibling) && is_block_side(node.next_sibling)
+}
+
+/// Checks if the node is inside a preformatted element (`pre`, `textarea`),
+/// where whitespace is significant.
+pub(crate) fn is_preformatted(nodes: &[TreeNode], id: &NodeId) -> bool {
+    let mut ancestor = nodes
+        .get(id.value)
+        .and_then(|node| node.parent)
+        .and_then(|id| nodes.get(id.value));
+    while let Some(node) = ancestor {
+        if let Some(el) = node.as_element() {
+            if PREFORMATTED_ELEMENTS.contains(&el.name.local.as_ref()) {
+                return true;
+            }
+        }
+        ancestor = node.parent.and_then(|id| nodes.get(id.value));
+    }
+    false
+}
+
+/// Collapses every run of whitespace characters into a single space.
+pub(crate) fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_whitespace = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !in_whitespace {
+                collapsed.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            collapsed.push(c);
+            in_whitespace = false;
+        }
+    }
+    collapsed
+}
+
+/// Counts the characters of the text as if every run of whitespace characters was collapsed into a single space.
+pub(crate) fn normalized_char_count(text: &str) -> usize {
+    let mut count = 0;
+    let mut in_whitespace = false;
+    for c in text.chars() {
+        let is_whitespace = c.is_whitespace();
+        if !(is_whitespace && in_whitespace) {
+            count += 1;
+        }
+        in_whitespace = is_whitespace;
+    }
+    count
+}
+
+enum FormatOp {
+    Open(NodeId),
+    Close(NodeId),
+}
+
+struct TextFormatter<'a> {
+    text: String,
+    pending_space: bool,
+    pre_depth: usize,
+    link_fmt: Option<&'a dyn Fn(&str) -> String>,
+}
+
+impl<'a> TextFormatter<'a> {
+    fn new(link_fmt: Option<&'a dyn Fn(&str) -> String>) -> Self {
+        Self {
+            text: String::new(),
+            pending_space: false,
+            pre_depth: 0,
+            link_fmt,
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if self.pre_depth > 0 {
+            self.text.push_str(text);
+            self.pending_space = false;
+            return;
+        }
+
+        for (i, word) in text.split_whitespace().enumerate() {
+            if i > 0 || text.starts_with(char::is_whitespace) {
+                self.pending_space = true;
+            }
+            if self.pending_space && !self.text.is_empty() && !self.text.ends_with([' ', '\n']) {
+                self.text.push(' ');
+            }
+            self.pending_space = false;
+            self.text.push_str(word);
+        }
+
+        if text.ends_with(char::is_whitespace) {
+            self.pending_space = true;
+        }
+    }
+
+    /// Ensures that the text ends with at least `count` line breaks.
+    fn ensure_newlines(&mut self, count: usize) {
+        self.pending_space = false;
+        if self.text.is_empty() {
+            return;
+        }
+        let trimmed_len = self.text.trim_end_matches(' ').len();
+        self.text.truncate(trimmed_len);
+
+        let existing = self.text.len() - self.text.trim_end_matches('\n').len();
+        for _ in existing..count {
+            self.text.push('\n');
+        }
+    }
+
+    fn open(&mut self, name: &str) {
+        match name {
+            "br" => {
+                let trimmed_len = self.text.trim_end_matches(' ').len();
+                self.text.truncate(trimmed_len);
+                self.text.push('\n');
+                self.pending_space = false;
+            }
+            "pre" => {
+                self.ensure_newlines(2);
+                self.pre_depth += 1;
+            }
+            _ if BLOCK_ELEMENTS.contains(&name) => self.ensure_newlines(2),
+            _ if LINE_ELEMENTS.contains(&name) => self.ensure_newlines(1),
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, node: &TreeNode, name: &str) {
+        match name {
+            "a" => {
+                let Some(link_fmt) = self.link_fmt else {
+                    return;
+                };
+                let Some(href) = node.as_element().and_then(|el| el.attr("href")) else {
+                    return;
+                };
+                if !href.is_empty() {
+                    self.text.push_str(&link_fmt(&href));
+                }
+            }
+            "pre" => {
+                self.pre_depth = self.pre_depth.saturating_sub(1);
+                self.ensure_newlines(2);
+            }
+            "td" | "th" => self.pending_space = true,
+            _ if BLOCK_ELEMENTS.contains(&name) => self.ensure_newlines(2),
+            _ if LINE_ELEMENTS.contains(&name) => self.ensure_newlines(1),
+            _ => {}
+        }
+    }
+
+    fn finish(self) -> StrTendril {
+        StrTendril::from(self.text.trim())
+    }
+}
+
+/// Collects the text of the node and its descendants, formatting it in a way
+/// similar to how a browser would render it.
+///
+/// - Whitespace is collapsed, so there is only one space between words.
+/// - Block elements (`p`, `div`, `h1`, etc.) are separated by a blank line.
+/// - `br` produces a line break, `li`, `tr`, `dt`, `dd` and `hr` are placed on their own line.
+/// - Contents of `pre` elements are kept as is.
+/// - Contents of `script`, `style` and `noscript` elements are skipped.
+///
+/// If `link_fmt` is provided, its result is appended right after the text of every `a` element
+/// with a non-empty `href` attribute. The function receives the value of the `href` attribute.
+pub(crate) fn format_text(
+    nodes: Ref<Vec<TreeNode>>,
+    id: NodeId,
+    link_fmt: Option<&dyn Fn(&str) -> String>,
+) -> StrTendril {
+    let mut formatter = TextFormatter::new(link_fmt);
+    let mut ops = vec![FormatOp::Open(id)];
+
+    while let Some(op) = ops.pop() {
+        match op {
+            FormatOp::Open(id) => {
+                let Some(node) = nodes.get(id.value) else {
+                    continue;
+                };
+                match node.data {
+                    NodeData::Document | NodeData::Fragment => {
+                        ops.extend(child_nodes(Ref::clone(&nodes), &id, true).map(FormatOp::Open));
+                    }
+                    NodeData::Element(ref el) => {
+                        let name = el.name.local.as_ref();
+                        if SKIP_ELEMENTS.contains(&name) {
+                            continue;
+                        }
+                        formatter.open(name);
+                        ops.push(FormatOp::Close(id));
+                        ops.extend(child_nodes(Ref::clone(&nodes), &id, true).map(FormatOp::Open));
+                    }
+                    NodeData::Text { ref contents } => formatter.push_text(contents),
+                    _ => continue,
+                }
+            }
+            FormatOp::Close(id) => {
+                let Some(node) = nodes.get(id.value) else {
+                    continue;
+                };
+                if let Some(el) = node.as_element() {
+                    formatter.close(node, el.name.local.as_ref());
+                }
+            }
+        }
+    }
+    formatter.finish()
+}
+
+/// Escapes HTML special characters (`&`, `<`, `>`, `"`, `'`) of the text.
+pub(crate) fn escape_html(text: &str) -> StrTendril {
+    let mut escaped = StrTendril::new();
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_slice("&amp;"),
+            '<' => escaped.push_slice("&lt;"),
+            '>' => escaped.push_slice("&gt;"),
+            '"' => escaped.push_slice("&quot;"),
+            '\'' => escaped.push_slice("&#39;"),
+            _ => escaped.push_char(c),
+        }
+    }
+    escaped
+}
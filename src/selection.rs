@@ -1,13 +1,17 @@
 use std::cell::Ref;
-use std::ops::Deref;
+use std::ops::{Bound, Deref, RangeBounds};
+use std::str::FromStr;
 use std::vec::IntoIter;
 
 use html5ever::Attribute;
 use tendril::StrTendril;
 
 use crate::document::Document;
+use crate::entities::{wrap_tendril, InnerHashSet};
 use crate::matcher::{MatchScope, Matcher, Matches};
-use crate::node::{ancestor_nodes, child_nodes, NodeId, NodeRef, TreeNode};
+use crate::node::{
+    ancestor_nodes, child_nodes, descendant_nodes, NodeData, NodeId, NodeRef, TreeNode,
+};
 use crate::{Tree, TreeNodeOps};
 
 /// Selection represents a collection of nodes matching some criteria. The
@@ -60,6 +64,25 @@ impl Selection<'_> {
         self.attr(name).unwrap_or_else(|| StrTendril::from(default))
     }
 
+    /// Gets the specified attribute's value for the first element in the selection, parsed into `T`.
+    /// Returns `None` if the selection is empty, the attribute is missing or its value fails to parse.
+    pub fn attr_as<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.nodes().first().and_then(|node| node.attr_as(name))
+    }
+
+    /// Gets the specified attribute's value for the first element in the selection as a boolean.
+    /// See [`NodeRef::attr_as_bool`] for the rules.
+    pub fn attr_as_bool(&self, name: &str) -> bool {
+        self.nodes()
+            .first()
+            .map_or(false, |node| node.attr_as_bool(name))
+    }
+
+    /// Gets the specified attribute's value for the first element in the selection, parsed as an integer.
+    pub fn attr_as_int(&self, name: &str) -> Option<i64> {
+        self.nodes().first().and_then(|node| node.attr_as_int(name))
+    }
+
     /// Sets the given attribute to each element in the set of matched elements.
     pub fn set_attr(&self, name: &str, val: &str) {
         self.update_nodes(|tree_node| {
@@ -67,6 +90,63 @@ impl Selection<'_> {
         });
     }
 
+    /// Sets the given attributes to each element in the set of matched elements.
+    /// Existing values are overwritten; if a name occurs more than once, the last value wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<a href="/old">Link</a>"#);
+    /// let sel = doc.select("a");
+    /// sel.set_attrs(&[("href", "/new"), ("target", "_blank"), ("rel", "noopener")]);
+    /// assert_eq!(sel.html(), r#"<a href="/new" target="_blank" rel="noopener">Link</a>"#.into());
+    /// ```
+    pub fn set_attrs(&self, attrs: &[(&str, &str)]) {
+        self.update_nodes(|tree_node| {
+            tree_node.set_attrs(attrs);
+        });
+    }
+
+    /// Transforms the value of the named attribute of each element in the set of matched elements.
+    ///
+    /// The current value is passed to `f`. If `f` returns `Some`, the value is replaced with it,
+    /// if `f` returns `None`, the attribute is removed from the element.
+    /// Elements that don't have the attribute are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<a href="/a">A</a><a href="">B</a><a>C</a>"#);
+    /// let sel = doc.select("a");
+    /// sel.map_attr("href", |href| {
+    ///     (!href.is_empty()).then(|| format!("https://example.com{}", href))
+    /// });
+    /// assert_eq!(
+    ///     doc.select("a[href^='https://example.com/']").length(),
+    ///     1
+    /// );
+    /// assert_eq!(doc.select("a[href]").length(), 1);
+    /// ```
+    pub fn map_attr<F>(&self, name: &str, f: F)
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        self.update_nodes(|tree_node| {
+            if let Some(el) = tree_node.as_element_mut() {
+                if let Some(val) = el.attr(name) {
+                    match f(&val) {
+                        Some(new_val) => el.set_attr(name, &new_val),
+                        None => el.remove_attr(name),
+                    }
+                }
+            }
+        });
+    }
+
     /// Removes the named attribute from each element in the set of matched elements.
     pub fn remove_attr(&self, name: &str) {
         self.update_nodes(|tree_node| {
@@ -95,6 +175,46 @@ impl Selection<'_> {
         });
     }
 
+    /// Renames all descendant elements with the tag `from` to `to`
+    /// for each element in the set of matched elements.
+    /// The matched elements themselves are not renamed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<div><b>Bold</b> and <i>italic</i></div>");
+    /// let sel = doc.select("div");
+    /// sel.rename_descendants("b", "strong");
+    /// assert_eq!(sel.inner_html().as_ref(), "<strong>Bold</strong> and <i>italic</i>");
+    /// ```
+    pub fn rename_descendants(&self, from: &str, to: &str) {
+        let Some(tree) = self.get_tree() else {
+            return;
+        };
+        let ids: Vec<NodeId> = {
+            let tree_nodes = tree.nodes.borrow();
+            self.nodes()
+                .iter()
+                .flat_map(|node| descendant_nodes(Ref::clone(&tree_nodes), &node.id))
+                .filter(|id| {
+                    tree_nodes
+                        .get(id.value)
+                        .and_then(|node| node.as_element())
+                        .map_or(false, |el| el.name.local.as_ref() == from)
+                })
+                .collect()
+        };
+
+        let mut tree_nodes = tree.nodes.borrow_mut();
+        for id in ids {
+            if let Some(tree_node) = tree_nodes.get_mut(id.value) {
+                tree_node.rename(to);
+            }
+        }
+    }
+
     /// Adds the given class to each element in the set of matched elements.
     /// Multiple class names can be specified, separated by a space via multiple arguments.
     pub fn add_class(&self, class: &str) {
@@ -109,6 +229,25 @@ impl Selection<'_> {
         self.nodes().iter().any(|node| node.has_class(class))
     }
 
+    /// Determines whether any of the matched elements are assigned all the given classes.
+    /// Entries containing whitespace are split into separate classes.
+    /// An empty slice returns `true` if the selection contains at least one element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<button class="btn primary large">OK</button>"#);
+    /// let sel = doc.select("button");
+    /// assert!(sel.has_classes(&["btn", "primary"]));
+    /// assert!(sel.has_classes(&["large primary"]));
+    /// assert!(!sel.has_classes(&["btn", "secondary"]));
+    /// ```
+    pub fn has_classes(&self, classes: &[&str]) -> bool {
+        self.nodes().iter().any(|node| node.has_classes(classes))
+    }
+
     /// Removes the given class from each element in the set of matched elements.
     /// Multiple class names can be specified, separated by a space via multiple arguments.
     pub fn remove_class(&self, class: &str) {
@@ -117,6 +256,36 @@ impl Selection<'_> {
         });
     }
 
+    /// Replaces the class `old` with `new` on each element in the set of matched elements that has `old`,
+    /// keeping the order of the other classes. Elements without `old` are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div class="a old b">One</div><div class="c">Two</div>"#);
+    /// let sel = doc.select("div");
+    /// sel.replace_class("old", "new");
+    /// assert_eq!(sel.first().attr("class").unwrap().as_ref(), "a new b");
+    /// assert_eq!(sel.last().attr("class").unwrap().as_ref(), "c");
+    /// ```
+    pub fn replace_class(&self, old: &str, new: &str) {
+        self.update_nodes(|tree_node| {
+            tree_node.replace_class(old, new);
+        });
+    }
+
+    /// Toggles the given class on each element in the set of matched elements:
+    /// the class is removed if the element has it, and added otherwise.
+    /// Each element is toggled according to its own state.
+    /// Multiple class names can be specified, separated by whitespace; each one is toggled independently.
+    pub fn toggle_class(&self, class: &str) {
+        self.update_nodes(|tree_node| {
+            tree_node.toggle_class(class);
+        });
+    }
+
     /// Returns the number of elements in the selection object.
     pub fn length(&self) -> usize {
         self.nodes().len()
@@ -176,6 +345,87 @@ impl Selection<'_> {
     pub fn immediate_text(&self) -> StrTendril {
         self.text_fn(TreeNodeOps::immediate_text_of)
     }
+
+    /// Returns the browser-like text (see [`NodeRef::inner_text`]) of each element
+    /// in the set of matched elements separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<ul><li>First<br>line</li><li>  Second  </li></ul>");
+    /// let texts = doc.select("li").inner_texts();
+    /// assert_eq!(texts, vec!["First\nline".into(), "Second".into()]);
+    /// ```
+    pub fn inner_texts(&self) -> Vec<StrTendril> {
+        self.nodes().iter().map(|node| node.inner_text()).collect()
+    }
+
+    /// Joins the trimmed text of each element in the set of matched elements with the separator.
+    /// Elements without text (or with whitespace only) are skipped,
+    /// so the result has no leading, trailing or doubled separators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<table><tr><td> a </td></tr><tr><td> </td></tr><tr><td>b</td></tr></table>");
+    /// assert_eq!(doc.select("tr").text_joined_by_node("\n").as_ref(), "a\nb");
+    /// ```
+    pub fn text_joined_by_node(&self, sep: &str) -> StrTendril {
+        let mut text = StrTendril::new();
+        for node in self.nodes() {
+            let node_text = node.text();
+            let node_text = node_text.trim();
+            if node_text.is_empty() {
+                continue;
+            }
+            if !text.is_empty() {
+                text.push_slice(sep);
+            }
+            text.push_slice(node_text);
+        }
+        text
+    }
+
+    /// Collects the links from the set of matched elements, including their descendants.
+    ///
+    /// # Returns
+    ///
+    /// `Vec<(StrTendril, StrTendril)>` -- a vector of pairs of the trimmed text and the `href` attribute value
+    /// of every `<a>` element in document order. Elements without `href` attribute are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(
+    ///     r#"<p><a href="/docs"> Docs </a>, <a name="top">top</a>, <a href="/api">API</a></p>"#,
+    /// );
+    /// let links = doc.select("p").extract_links();
+    /// assert_eq!(
+    ///     links,
+    ///     vec![("Docs".into(), "/docs".into()), ("API".into(), "/api".into())]
+    /// );
+    /// ```
+    pub fn extract_links(&self) -> Vec<(StrTendril, StrTendril)> {
+        let matcher = Matcher::new("a[href]").expect("Invalid CSS selector");
+        // `Matches` takes root nodes from the end
+        Matches::from_list(
+            self.nodes.iter().rev().cloned(),
+            &matcher,
+            MatchScope::IncludeNode,
+        )
+        .filter_map(|node| {
+            let href = node.attr("href")?;
+            let text = StrTendril::from(node.text().trim());
+            Some((text, href))
+        })
+        .collect()
+    }
 }
 
 //matching methods
@@ -190,7 +440,11 @@ impl<'a> Selection<'a> {
     /// returns true if at least one of these elements matches.
     pub fn is_matcher(&self, matcher: &Matcher) -> bool {
         if self.length() > 0 {
-            return self.nodes().iter().any(|node| matcher.match_element(node));
+            let mut caches = Default::default();
+            return self
+                .nodes()
+                .iter()
+                .any(|node| matcher.match_element_with_caches(node, &mut caches));
         }
         false
     }
@@ -205,6 +459,40 @@ impl<'a> Selection<'a> {
         self.nodes().iter().any(|node| m.contains(&node.id.value))
     }
 
+    /// Returns the tag name shared by all nodes in the selection.
+    ///
+    /// Returns `None` if the selection is empty, contains nodes that are not elements,
+    /// or its elements have different names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<a href="/1">1</a><a href="/2">2</a><span>3</span>"#);
+    /// assert_eq!(doc.select("a").closest_common_tag(), Some("a".into()));
+    /// assert_eq!(doc.select("a, span").closest_common_tag(), None);
+    /// ```
+    pub fn closest_common_tag(&self) -> Option<StrTendril> {
+        let (first, rest) = self.nodes().split_first()?;
+        let name = first.node_name()?;
+        rest.iter()
+            .all(|node| node.node_name().as_ref() == Some(&name))
+            .then_some(name)
+    }
+
+    /// Checks if both selections contain exactly the same set of nodes, regardless of their order.
+    ///
+    /// Unlike [`Selection::is_selection`], which checks if selections overlap,
+    /// it requires every node of each selection to be present in the other one.
+    /// Two empty selections are considered the same.
+    pub fn same_nodes_as(&self, other: &Selection) -> bool {
+        let ids: InnerHashSet<usize> = self.nodes().iter().map(|node| node.id.value).collect();
+        let other_ids: InnerHashSet<usize> =
+            other.nodes().iter().map(|node| node.id.value).collect();
+        ids == other_ids
+    }
+
     /// Filters the current set of matched elements to those that match the
     /// given CSS selector.
     ///
@@ -256,15 +544,58 @@ impl<'a> Selection<'a> {
         if self.is_empty() {
             return self.clone();
         }
+        let mut caches = Default::default();
         let nodes = self
             .nodes()
             .iter()
-            .filter(|&node| matcher.match_element(node))
+            .filter(|&node| matcher.match_element_with_caches(node, &mut caches))
             .cloned()
             .collect();
         Selection { nodes }
     }
 
+    /// Splits the current set of matched elements into those that match the
+    /// given CSS selector and those that don't, in a single pass.
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Returns
+    ///
+    /// A pair of new Selection objects: the matched elements and the rest of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<a href="/a" rel="nofollow">A</a><a href="/b">B</a>"#);
+    /// let (nofollow, other) = doc.select("a").partition("[rel~=nofollow]");
+    /// assert_eq!(nofollow.text().as_ref(), "A");
+    /// assert_eq!(other.text().as_ref(), "B");
+    /// ```
+    pub fn partition(&self, sel: &str) -> (Selection<'a>, Selection<'a>) {
+        let matcher = Matcher::new(sel).expect("Invalid CSS selector");
+        self.partition_matcher(&matcher)
+    }
+
+    /// Splits the current set of matched elements into those that match the
+    /// given matcher and those that don't, in a single pass.
+    ///
+    /// # Returns
+    ///
+    /// A pair of new Selection objects: the matched elements and the rest of them.
+    pub fn partition_matcher(&self, matcher: &Matcher) -> (Selection<'a>, Selection<'a>) {
+        let mut caches = Default::default();
+        let (matched, rest) = self
+            .nodes()
+            .iter()
+            .cloned()
+            .partition(|node| matcher.match_element_with_caches(node, &mut caches));
+        (Selection { nodes: matched }, Selection { nodes: rest })
+    }
+
     /// Reduces the set of matched elements to those that match a node in the specified `Selection`.
     /// It returns a new `Selection` for this subset of elements.
     pub fn filter_selection(&self, other: &Selection) -> Selection<'a> {
@@ -281,6 +612,232 @@ impl<'a> Selection<'a> {
         Selection { nodes }
     }
 
+    /// Removes elements matching the given CSS selector from the current set of matched elements.
+    /// It is the opposite of [`Selection::filter`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Returns
+    ///
+    /// A new Selection object containing the rest of the elements, in the same order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<ul><li>One</li><li class="disabled">Two</li><li>Three</li></ul>"#);
+    /// let enabled = doc.select("li").not(".disabled");
+    /// assert_eq!(enabled.text().as_ref(), "OneThree");
+    /// ```
+    pub fn not(&self, sel: &str) -> Selection<'a> {
+        if self.is_empty() {
+            return self.clone();
+        }
+        let matcher = Matcher::new(sel).expect("Invalid CSS selector");
+        self.not_matcher(&matcher)
+    }
+
+    /// Removes elements matching the given CSS selector from the current set of matched elements.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the selector was invalid, otherwise a new `Selection` object containing the rest of the elements.
+    pub fn try_not(&self, sel: &str) -> Option<Selection<'a>> {
+        if self.is_empty() {
+            return Some(self.clone());
+        }
+        Matcher::new(sel).ok().map(|m| self.not_matcher(&m))
+    }
+
+    /// Removes elements matching the given matcher from the current set of matched elements.
+    ///
+    /// # Returns
+    ///
+    /// A new Selection object containing the rest of the elements, in the same order.
+    pub fn not_matcher(&self, matcher: &Matcher) -> Selection<'a> {
+        let mut caches = Default::default();
+        let nodes = self
+            .nodes()
+            .iter()
+            .filter(|&node| !matcher.match_element_with_caches(node, &mut caches))
+            .cloned()
+            .collect();
+        Selection { nodes }
+    }
+
+    /// Removes elements that are present in the specified `Selection` from the current set of matched elements.
+    /// It is the opposite of [`Selection::filter_selection`].
+    /// It returns a new `Selection` for the rest of the elements.
+    pub fn not_selection(&self, other: &Selection) -> Selection<'a> {
+        if self.is_empty() || other.is_empty() {
+            return self.clone();
+        }
+        let m: Vec<usize> = other.nodes().iter().map(|node| node.id.value).collect();
+        let nodes = self
+            .nodes()
+            .iter()
+            .filter(|&node| !m.contains(&node.id.value))
+            .cloned()
+            .collect();
+        Selection { nodes }
+    }
+
+    /// Reduces the current set of matched elements to those that have a descendant
+    /// matching the given CSS selector. The elements themselves are not tested.
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div id="a"><img src="a.png"></div><div id="b"><p>Text</p></div>"#);
+    /// let with_images = doc.select("div").has("img");
+    /// assert_eq!(with_images.attr("id").unwrap().as_ref(), "a");
+    /// ```
+    pub fn has(&self, sel: &str) -> Selection<'a> {
+        if self.is_empty() {
+            return self.clone();
+        }
+        let matcher = Matcher::new(sel).expect("Invalid CSS selector");
+        self.has_matcher(&matcher)
+    }
+
+    /// Reduces the current set of matched elements to those that have a descendant
+    /// matching the given CSS selector.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the selector was invalid, otherwise a new `Selection` object containing the matched elements.
+    pub fn try_has(&self, sel: &str) -> Option<Selection<'a>> {
+        if self.is_empty() {
+            return Some(self.clone());
+        }
+        Matcher::new(sel).ok().map(|m| self.has_matcher(&m))
+    }
+
+    /// Reduces the current set of matched elements to those that have a descendant
+    /// matching the given matcher. Unlike the `:has()` pseudo-class, it allows to reuse an already built matcher.
+    pub fn has_matcher(&self, matcher: &Matcher) -> Selection<'a> {
+        let nodes = self
+            .nodes()
+            .iter()
+            .filter(|&node| {
+                Matches::from_one(node.clone(), matcher, MatchScope::ChildrenOnly)
+                    .next()
+                    .is_some()
+            })
+            .cloned()
+            .collect();
+        Selection { nodes }
+    }
+
+    /// Reduces the set of matched elements to those that have the given attribute.
+    /// It is a method form of the `[name]` CSS selector.
+    /// It returns a new `Selection` for this subset of elements.
+    pub fn with_attr(&self, name: &str) -> Selection<'a> {
+        let nodes = self
+            .nodes()
+            .iter()
+            .filter(|node| node.has_attr(name))
+            .cloned()
+            .collect();
+        Selection { nodes }
+    }
+
+    /// Reduces the set of matched elements to those that don't have the given attribute.
+    /// It is a method form of the `:not([name])` CSS selector.
+    /// It returns a new `Selection` for this subset of elements.
+    pub fn without_attr(&self, name: &str) -> Selection<'a> {
+        let nodes = self
+            .nodes()
+            .iter()
+            .filter(|node| !node.has_attr(name))
+            .cloned()
+            .collect();
+        Selection { nodes }
+    }
+
+    /// Reduces the set of matched elements to those whose attribute value contains the given substring.
+    /// It returns a new `Selection` for this subset of elements.
+    ///
+    /// It works like the `[name*="substr"]` CSS selector, but the substring doesn't need to be escaped,
+    /// so it is convenient when the substring comes from a variable. Unlike CSS, an empty substring
+    /// keeps every element having the attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<a href="/search?q=a&b">1</a><a href="/about">2</a>"#);
+    /// let sel = doc.select("a").filter_attr_contains("href", "q=a&b");
+    /// assert_eq!(sel.text().as_ref(), "1");
+    /// ```
+    pub fn filter_attr_contains(&self, name: &str, substr: &str) -> Selection<'a> {
+        let nodes = self
+            .nodes()
+            .iter()
+            .filter(|node| node.attr(name).map_or(false, |val| val.contains(substr)))
+            .cloned()
+            .collect();
+        Selection { nodes }
+    }
+
+    /// Reduces the set of matched elements to those that are likely to be visible to the user.
+    /// It returns a new `Selection` for this subset of elements.
+    ///
+    /// See [`NodeRef::is_visible`] for details of the heuristic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p>Shown</p><div hidden><p>Hidden</p></div>"#);
+    /// assert_eq!(doc.select("p").visible().text().as_ref(), "Shown");
+    /// ```
+    pub fn visible(&self) -> Selection<'a> {
+        let nodes = self
+            .nodes()
+            .iter()
+            .filter(|node| node.is_visible())
+            .cloned()
+            .collect();
+        Selection { nodes }
+    }
+
+    /// Returns a new `Selection` with the same nodes, ordered by the key extracted with `f`.
+    ///
+    /// The sort is stable: nodes with equal keys keep their original order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p data-n="3">c</p><p data-n="1">a</p><p data-n="2">b</p>"#);
+    /// let sel = doc
+    ///     .select("p")
+    ///     .sort_by_key(|node| node.attr("data-n").and_then(|n| n.parse::<u32>().ok()));
+    /// assert_eq!(sel.text().as_ref(), "abc");
+    /// ```
+    pub fn sort_by_key<K, F>(&self, f: F) -> Selection<'a>
+    where
+        K: Ord,
+        F: Fn(&NodeRef) -> K,
+    {
+        let mut nodes = self.nodes().to_vec();
+        nodes.sort_by_key(|node| f(node));
+        Selection { nodes }
+    }
+
     /// Adds nodes that match the given CSS selector to the current selection.
     ///
     /// # Panics
@@ -391,6 +948,35 @@ impl Selection<'_> {
         });
     }
 
+    /// Removes the matched elements that are empty (see [`NodeRef::is_empty_element`])
+    /// from the document. Non-empty elements stay in place.
+    /// Note that void elements, like `img` or `br`, are always empty.
+    ///
+    /// # Returns
+    ///
+    /// The number of removed elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<div><p>Text</p><p> </p><p></p></div>");
+    /// assert_eq!(doc.select("p").remove_empty(), 2);
+    /// assert_eq!(doc.select("div").inner_html().as_ref(), "<p>Text</p>");
+    /// ```
+    pub fn remove_empty(&self) -> usize {
+        let empty: Vec<&NodeRef> = self
+            .nodes()
+            .iter()
+            .filter(|node| node.is_empty_element())
+            .collect();
+        for node in empty.iter() {
+            node.remove_from_parent();
+        }
+        empty.len()
+    }
+
     /// Replaces each element in the set of matched element with
     /// the nodes from the given selection.
     ///
@@ -447,12 +1033,42 @@ impl Selection<'_> {
         });
     }
 
-    /// Replaces each element in the set of matched elements with
-    /// the parsed HTML.
+    /// Sets the html contents of each element in the selection to its own parsed HTML:
+    /// the n-th element gets the n-th item of `htmls`, like [`Selection::set_html`] does for a single HTML.
     ///
-    /// This follows the same rules as `append`.
-    pub fn replace_with_html<T: Into<StrTendril>>(&self, html: T) {
-        self.merge_html_with_fn(html, |tree_nodes, new_node_id, node| {
+    /// Returns `true` if the contents were set. If the number of elements differs from the length of `htmls`,
+    /// nothing is changed and `false` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div class="card"></div><div class="card"></div>"#);
+    /// let cards = doc.select(".card");
+    /// assert!(cards.set_inner_html_each(&["<h3>One</h3>", "<h3>Two</h3>"]));
+    /// assert_eq!(
+    ///     doc.select("body").inner_html().as_ref(),
+    ///     r#"<div class="card"><h3>One</h3></div><div class="card"><h3>Two</h3></div>"#
+    /// );
+    /// assert!(!cards.set_inner_html_each(&["<h3>Only one</h3>"]));
+    /// ```
+    pub fn set_inner_html_each(&self, htmls: &[&str]) -> bool {
+        if self.length() != htmls.len() {
+            return false;
+        }
+        for (node, html) in self.nodes().iter().zip(htmls) {
+            node.set_html(*html);
+        }
+        true
+    }
+
+    /// Replaces each element in the set of matched elements with
+    /// the parsed HTML.
+    ///
+    /// This follows the same rules as `append`.
+    pub fn replace_with_html<T: Into<StrTendril>>(&self, html: T) {
+        self.merge_html_with_fn(html, |tree_nodes, new_node_id, node| {
             TreeNodeOps::insert_siblings_before(tree_nodes, &node.id, &new_node_id);
             TreeNodeOps::remove_from_parent(tree_nodes, &node.id);
         });
@@ -486,6 +1102,194 @@ impl Selection<'_> {
         });
     }
 
+    /// Removes every descendant element of the set of matched elements, that matches the selector,
+    /// promoting its children to its place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p>Some <span class="hl">highlighted <b>text</b></span>.</p>"#);
+    /// let sel = doc.select("p");
+    /// sel.unwrap_all_matching("span.hl");
+    /// assert_eq!(sel.inner_html().as_ref(), "Some highlighted <b>text</b>.");
+    /// ```
+    pub fn unwrap_all_matching(&self, sel: &str) {
+        let matcher = Matcher::new(sel).expect("Invalid CSS selector");
+        let targets = self.select_matcher(&matcher);
+        let Some(tree) = targets.get_tree() else {
+            return;
+        };
+        let mut nodes = tree.nodes.borrow_mut();
+        for node in targets.nodes() {
+            if let Some(first_child_id) = nodes.get(node.id.value).and_then(|n| n.first_child) {
+                TreeNodeOps::insert_siblings_before(&mut nodes, &node.id, &first_child_id);
+            }
+            TreeNodeOps::remove_from_parent(&mut nodes, &node.id);
+        }
+    }
+
+    /// Merges adjacent elements of the set of matched elements:
+    /// if the next sibling of a matched element is also matched and has the same name,
+    /// its children are moved into the first element, and it is removed from the tree.
+    /// Runs of more than two adjacent elements are merged into the first one.
+    ///
+    /// Only direct siblings are merged: even a whitespace text node between elements keeps them apart.
+    /// Attributes of the removed elements are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p><b>a</b><b>b</b><b>c</b> <b>d</b></p>"#);
+    /// doc.select("b").merge_adjacent();
+    /// assert_eq!(doc.select("p").inner_html().as_ref(), "<b>abc</b> <b>d</b>");
+    /// ```
+    pub fn merge_adjacent(&self) {
+        let Some(tree) = self.get_tree() else {
+            return;
+        };
+        let mut matched: InnerHashSet<usize> = self.nodes().iter().map(|n| n.id.value).collect();
+        let mut nodes = tree.nodes.borrow_mut();
+        for node in self.nodes() {
+            if !matched.contains(&node.id.value) {
+                continue;
+            }
+            let Some(name) = nodes[node.id.value].as_element().map(|el| el.name.clone()) else {
+                continue;
+            };
+            while let Some(next_id) = nodes[node.id.value].next_sibling {
+                let is_same = matched.contains(&next_id.value)
+                    && nodes[next_id.value]
+                        .as_element()
+                        .map_or(false, |el| el.name == name);
+                if !is_same {
+                    break;
+                }
+                if let Some(first_child_id) = nodes[next_id.value].first_child {
+                    TreeNodeOps::append_children_of(&mut nodes, &node.id, &first_child_id);
+                }
+                TreeNodeOps::remove_from_parent(&mut nodes, &next_id);
+                matched.remove(&next_id.value);
+            }
+        }
+    }
+
+    /// Tidies up each element in the set of matched elements before serialization:
+    /// merges adjacent text nodes and removes whitespace-only text nodes between block elements.
+    ///
+    /// See [`NodeRef::html_tidy`] for details.
+    pub fn html_tidy(&self) {
+        for node in self.nodes() {
+            node.html_tidy();
+        }
+    }
+
+    /// Parses the html and wraps each element in the set of matched elements
+    /// with its own copy of it. It works like `wrap()` in jQuery.
+    ///
+    /// See [`NodeRef::wrap_html`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p><img src="a.png"><img src="b.png"></p>"#);
+    /// doc.select("img").wrap_each_html(r#"<a class="lightbox"></a>"#);
+    /// assert_eq!(doc.select("p > a.lightbox > img").length(), 2);
+    /// ```
+    pub fn wrap_each_html<T: Into<StrTendril>>(&self, html: T) {
+        self.merge_html_with_fn(html, |tree_nodes, new_node_id, node| {
+            TreeNodeOps::wrap_with(tree_nodes, &node.id, &new_node_id);
+        });
+    }
+
+    /// Wraps each element in the set of matched elements with its own copy of the wrapper element,
+    /// so one prepared wrapper serves many nodes. The wrapper itself is not changed,
+    /// and it may belong to another document.
+    ///
+    /// Each node is placed into the innermost first element of the wrapper copy.
+    /// If the wrapper is not an element, nothing happens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p><img src="a.png"><img src="b.png"></p>"#);
+    /// let wrapper = doc.tree.new_element("a");
+    /// wrapper.set_attr("class", "lightbox");
+    /// doc.select("img").wrap_with_node(&wrapper);
+    /// assert_eq!(doc.select("p > a.lightbox > img").length(), 2);
+    /// ```
+    pub fn wrap_with_node(&self, wrapper: &NodeRef) {
+        if !wrapper.is_element() {
+            return;
+        }
+        let Some(tree) = self.get_tree() else {
+            return;
+        };
+        for node in self.nodes() {
+            let wrapper_id = tree.copy_node(wrapper);
+            let mut nodes = tree.nodes.borrow_mut();
+            TreeNodeOps::wrap_with(&mut nodes, &node.id, &wrapper_id);
+        }
+    }
+
+    /// Transforms the contents of every text node within the set of matched elements.
+    ///
+    /// `f` receives the contents of a text node and returns a new one.
+    /// Attributes, element names, comments are not affected.
+    /// Note that contents of `<script>` and `<style>` elements are text nodes too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<p title="greeting">Hello <b>world</b>!</p>"#);
+    /// let sel = doc.select("p");
+    /// sel.text_map(|text| text.to_uppercase());
+    /// assert_eq!(sel.html().as_ref(), r#"<p title="greeting">HELLO <b>WORLD</b>!</p>"#);
+    /// ```
+    pub fn text_map<F>(&self, f: F)
+    where
+        F: Fn(&str) -> String,
+    {
+        let Some(tree) = self.get_tree() else {
+            return;
+        };
+        let text_ids: Vec<NodeId> = {
+            let nodes = tree.nodes.borrow();
+            let mut seen: InnerHashSet<usize> = InnerHashSet::default();
+            self.nodes()
+                .iter()
+                .flat_map(|node| {
+                    std::iter::once(node.id).chain(descendant_nodes(Ref::clone(&nodes), &node.id))
+                })
+                .filter(|id| nodes.get(id.value).map_or(false, |n| n.is_text()))
+                .filter(|id| seen.insert(id.value))
+                .collect()
+        };
+
+        let mut nodes = tree.nodes.borrow_mut();
+        for id in text_ids {
+            if let Some(NodeData::Text { ref mut contents }) =
+                nodes.get_mut(id.value).map(|n| &mut n.data)
+            {
+                let new_contents = f(contents);
+                *contents = wrap_tendril(StrTendril::from(new_contents));
+            }
+        }
+    }
+
     /// Sets the content of each element in the selection to specified content. Doesn't escapes the text.
     ///
     /// If simple text needs to be inserted, this method is preferable to [Selection::set_html],
@@ -495,6 +1299,36 @@ impl Selection<'_> {
             TreeNodeOps::set_text(nodes, id, text);
         });
     }
+
+    /// Replaces the children of each element in the selection with a single text node
+    /// containing the current text of the element, flattening all nested markup.
+    /// The text is escaped when the element is serialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<table><tr><td><b>1</b> &lt; <i>2</i></td></tr></table>"#);
+    /// let sel = doc.select("td");
+    /// sel.keep_only_text();
+    /// assert_eq!(sel.html().as_ref(), "<td>1 &lt; 2</td>");
+    /// ```
+    pub fn keep_only_text(&self) {
+        let Some(tree) = self.get_tree() else {
+            return;
+        };
+        let texts: Vec<(NodeId, StrTendril)> = self
+            .nodes()
+            .iter()
+            .filter(|node| node.is_element())
+            .map(|node| (node.id, node.text()))
+            .collect();
+        let mut nodes = tree.nodes.borrow_mut();
+        for (id, text) in texts {
+            TreeNodeOps::set_text(&mut nodes, &id, text);
+        }
+    }
 }
 
 // traversing methods
@@ -591,6 +1425,53 @@ impl<'a> Selection<'a> {
         Selections::new(self.nodes.clone().into_iter())
     }
 
+    /// Calls the closure with the index and the node of each matched element, in order,
+    /// and collects the results into a vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<a href="/a">A</a><a href="/b">B</a>"#);
+    /// let hrefs = doc.select("a").map(|i, node| format!("{}:{}", i, node.attr_or("href", "")));
+    /// assert_eq!(hrefs, vec!["0:/a", "1:/b"]);
+    /// ```
+    pub fn map<T, F>(&self, mut f: F) -> Vec<T>
+    where
+        F: FnMut(usize, &NodeRef<'a>) -> T,
+    {
+        let mut result = Vec::with_capacity(self.length());
+        for (i, node) in self.nodes().iter().enumerate() {
+            result.push(f(i, node));
+        }
+        result
+    }
+
+    /// Calls the closure with the index and the node of each matched element, in order.
+    /// Returns the selection itself, so calls can be chained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<ul><li>A</li><li>B</li></ul>");
+    /// let sel = doc.select("li");
+    /// sel.each(|i, node| node.set_attr("data-index", &i.to_string()))
+    ///     .add_class("item");
+    /// assert_eq!(doc.select(r#"li.item[data-index="1"]"#).text().as_ref(), "B");
+    /// ```
+    pub fn each<F>(&self, mut f: F) -> &Self
+    where
+        F: FnMut(usize, &NodeRef<'a>),
+    {
+        for (i, node) in self.nodes().iter().enumerate() {
+            f(i, node);
+        }
+        self
+    }
+
     /// Gets the parent of each element in the selection. It returns a
     /// mew Selection object containing these elements.
     pub fn parent(&self) -> Selection<'a> {
@@ -627,6 +1508,74 @@ impl<'a> Selection<'a> {
         Self { nodes: result }
     }
 
+    /// Gets all child nodes of each element in the selection, including text and comment nodes,
+    /// like `childNodes` in the DOM. It returns a new Selection object containing these nodes.
+    ///
+    /// Note that methods dealing with elements, like [`Selection::attr`] or [`Selection::add_class`],
+    /// have no effect on the text and comment nodes of the resulting selection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<p>Hello, <b>World</b><!-- note --></p>");
+    /// let contents = doc.select("p").contents();
+    /// assert_eq!(contents.length(), 3);
+    /// assert_eq!(doc.select("p").children().length(), 1);
+    /// ```
+    pub fn contents(&self) -> Selection<'a> {
+        let Some(first) = self.nodes().first() else {
+            return Default::default();
+        };
+
+        let mut set = Vec::with_capacity(self.length());
+        let tree_nodes = first.tree.nodes.borrow();
+
+        for node in self.nodes() {
+            for child_id in child_nodes(Ref::clone(&tree_nodes), &node.id, false) {
+                if !set.contains(&child_id) {
+                    set.push(child_id);
+                }
+            }
+        }
+
+        let result = set.iter().map(|id| NodeRef::new(*id, first.tree)).collect();
+        Self { nodes: result }
+    }
+
+    /// Gets the descendant text nodes of each element in the selection, in document order.
+    /// It returns a new Selection object containing these text nodes,
+    /// so they can be modified at once, e.g. with [`Selection::set_text`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<p>Hello <b>World</b></p>");
+    /// let texts = doc.select("p").text_nodes();
+    /// assert_eq!(texts.length(), 2);
+    /// assert!(texts.nodes().iter().all(|node| node.is_text()));
+    /// ```
+    pub fn text_nodes(&self) -> Selection<'a> {
+        let Some(first) = self.nodes().first() else {
+            return Default::default();
+        };
+
+        let tree_nodes = first.tree.nodes.borrow();
+        let mut seen: InnerHashSet<usize> = InnerHashSet::default();
+        let result = self
+            .nodes()
+            .iter()
+            .flat_map(|node| descendant_nodes(Ref::clone(&tree_nodes), &node.id))
+            .filter(|id| tree_nodes.get(id.value).map_or(false, |n| n.is_text()))
+            .filter(|id| seen.insert(id.value))
+            .map(|id| NodeRef::new(id, first.tree))
+            .collect();
+        Self { nodes: result }
+    }
+
     /// Gets the ancestor elements of each element in the selection.
     ///
     /// # Arguments
@@ -658,6 +1607,80 @@ impl<'a> Selection<'a> {
         Self { nodes: result }
     }
 
+    /// Returns the zero-based position of the first node in the selection among its element siblings.
+    /// Returns `None` if the selection is empty or the first node has no parent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<ul><li>One</li><li id="second">Two</li></ul>"#);
+    /// assert_eq!(doc.select("#second").index(), Some(1));
+    /// assert_eq!(doc.select("li").index(), Some(0));
+    /// assert_eq!(doc.select("table").index(), None);
+    /// ```
+    pub fn index(&self) -> Option<usize> {
+        let first = self.nodes().first()?;
+        first.parent()?;
+        Some(first.index())
+    }
+
+    /// Gets the ancestor elements of each element in the selection, from the parent up to the root,
+    /// optionally filtered by the CSS selector.
+    /// It returns a new Selection object containing these elements, without duplicates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<div class="a"><section><div class="b"><p>Text</p></div></section></div>"#);
+    /// let p = doc.select("p");
+    /// assert_eq!(p.parents(None).length(), 5);
+    /// let divs = p.parents(Some("div"));
+    /// assert_eq!(divs.length(), 2);
+    /// assert!(divs.first().has_class("b"));
+    /// ```
+    pub fn parents(&self, sel: Option<&str>) -> Selection<'a> {
+        let ancestors = self.ancestors(None);
+        match sel {
+            Some(sel) => ancestors.filter(sel),
+            None => ancestors,
+        }
+    }
+
+    /// Gets the ancestor elements of each element in the selection, from the parent up to
+    /// but not including the first ancestor matching the CSS selector.
+    /// If the selector is empty or invalid, all ancestors are collected.
+    /// It returns a new Selection object containing these elements, without duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<article><section><div><p>Text</p></div></section></article>"#);
+    /// let parents = doc.select("p").parents_until("article");
+    /// assert_eq!(parents.length(), 2);
+    /// assert!(parents.first().is("div"));
+    /// ```
+    pub fn parents_until(&self, sel: &str) -> Selection<'a> {
+        let matcher = Matcher::new(sel).ok();
+        self.ancestors_until(matcher.as_ref())
+    }
+
+    /// Gets the ancestor elements of each element in the selection, from the parent up to
+    /// but not including the first ancestor matching the matcher.
+    /// It returns a new Selection object containing these elements, without duplicates.
+    pub fn parents_until_matcher(&self, matcher: &Matcher) -> Selection<'a> {
+        self.ancestors_until(Some(matcher))
+    }
+
     #[deprecated(since = "0.1.6", note = "Please use `next_sibling`")]
     /// Gets the immediately following sibling of each element in the
     /// selection. It returns a new Selection object containing these elements.
@@ -683,6 +1706,230 @@ impl<'a> Selection<'a> {
         })
     }
 
+    /// Gets the following sibling elements of each element in the selection,
+    /// up to but not including the first element matching the CSS selector.
+    /// If the selector is empty or invalid, all following sibling elements are collected.
+    /// It returns a new Selection object containing these elements, without duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(
+    ///     r#"<table><tr class="header"><th>A</th></tr><tr><td>1</td></tr><tr><td>2</td></tr>
+    ///     <tr class="header"><th>B</th></tr><tr><td>3</td></tr></table>"#,
+    /// );
+    /// let rows = doc.select("tr.header").first().next_until(".header");
+    /// assert_eq!(rows.length(), 2);
+    /// assert_eq!(rows.text().as_ref(), "12");
+    /// ```
+    pub fn next_until(&self, sel: &str) -> Selection<'a> {
+        let matcher = Matcher::new(sel).ok();
+        self.siblings_until(matcher.as_ref(), false)
+    }
+
+    /// Gets the following sibling elements of each element in the selection,
+    /// up to but not including the first element matching the matcher.
+    /// It returns a new Selection object containing these elements, without duplicates.
+    pub fn next_until_matcher(&self, matcher: &Matcher) -> Selection<'a> {
+        self.siblings_until(Some(matcher), false)
+    }
+
+    /// Gets the preceding sibling elements of each element in the selection, nearest first,
+    /// up to but not including the first element matching the CSS selector.
+    /// If the selector is empty or invalid, all preceding sibling elements are collected.
+    /// It returns a new Selection object containing these elements, without duplicates.
+    pub fn prev_until(&self, sel: &str) -> Selection<'a> {
+        let matcher = Matcher::new(sel).ok();
+        self.siblings_until(matcher.as_ref(), true)
+    }
+
+    /// Gets the preceding sibling elements of each element in the selection, nearest first,
+    /// up to but not including the first element matching the matcher.
+    /// It returns a new Selection object containing these elements, without duplicates.
+    pub fn prev_until_matcher(&self, matcher: &Matcher) -> Selection<'a> {
+        self.siblings_until(Some(matcher), true)
+    }
+
+    /// Gets the sibling elements of each element in the selection, excluding the element itself.
+    /// It returns a new Selection object containing these elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<ul><li>One</li><li id=\"two\">Two</li>text<li>Three</li></ul>");
+    /// let siblings = doc.select("#two").siblings();
+    /// assert_eq!(siblings.length(), 2);
+    /// assert_eq!(siblings.text().as_ref(), "OneThree");
+    /// ```
+    pub fn siblings(&self) -> Selection<'a> {
+        let Some(first) = self.nodes().first() else {
+            return Default::default();
+        };
+
+        let mut set = Vec::with_capacity(self.length());
+        let tree_nodes = first.tree.nodes.borrow();
+
+        for node in self.nodes() {
+            let Some(parent_id) = tree_nodes.get(node.id.value).and_then(|n| n.parent) else {
+                continue;
+            };
+            for sibling in child_nodes(Ref::clone(&tree_nodes), &parent_id, false)
+                .flat_map(|id| tree_nodes.get(id.value))
+            {
+                if sibling.id != node.id && !set.contains(&sibling.id) && sibling.is_element() {
+                    set.push(sibling.id);
+                }
+            }
+        }
+
+        let result = set.iter().map(|id| NodeRef::new(*id, first.tree)).collect();
+        Self { nodes: result }
+    }
+
+    /// Gets the sibling elements of each element in the selection, excluding the element itself,
+    /// that match the given matcher.
+    /// It returns a new Selection object containing these elements.
+    pub fn siblings_matcher(&self, matcher: &Matcher) -> Selection<'a> {
+        self.siblings().filter_matcher(matcher)
+    }
+
+    /// Gets the closest element matching the given CSS selector for each element in the selection,
+    /// testing the element itself and then its ancestors, like `closest` in jQuery.
+    /// It returns a new Selection object containing these elements, without duplicates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if failed to parse the given CSS selector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<ul class="menu"><li><a href="/a">A</a></li><li><a href="/b">B</a></li></ul>"#);
+    /// assert_eq!(doc.select("a").closest("li").length(), 2);
+    /// assert_eq!(doc.select("a").closest("ul").length(), 1);
+    /// assert_eq!(doc.select("li").closest("li").length(), 2);
+    /// ```
+    pub fn closest(&self, sel: &str) -> Selection<'a> {
+        let matcher = Matcher::new(sel).expect("Invalid CSS selector");
+        self.closest_matcher(&matcher)
+    }
+
+    /// Gets the closest element matching the given matcher for each element in the selection,
+    /// testing the element itself and then its ancestors.
+    /// It returns a new Selection object containing these elements, without duplicates.
+    pub fn closest_matcher(&self, matcher: &Matcher) -> Selection<'a> {
+        let mut seen: InnerHashSet<usize> = InnerHashSet::default();
+        let nodes = self
+            .nodes()
+            .iter()
+            .filter_map(|node| node.closest(matcher))
+            .filter(|node| seen.insert(node.id.value))
+            .collect();
+        Selection { nodes }
+    }
+
+    /// Gets the nearest element having the given attribute for each element in the selection,
+    /// testing the element itself and then its ancestors.
+    /// It returns a new Selection object containing these elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from(r#"<section data-section="intro"><div><p>Text</p></div></section>"#);
+    /// let section = doc.select("p").closest_with_attr("data-section");
+    /// assert_eq!(section.attr("data-section").unwrap().as_ref(), "intro");
+    /// ```
+    pub fn closest_with_attr(&self, name: &str) -> Selection<'a> {
+        self.derive_selection(|tree_nodes, node| {
+            let mut next_id = Some(node.id);
+            while let Some(id) = next_id {
+                let tree_node = tree_nodes.get(id.value)?;
+                if tree_node.as_element().map_or(false, |el| el.has_attr(name)) {
+                    return Some(NodeRef::new(id, node.tree));
+                }
+                next_id = tree_node.parent;
+            }
+            None
+        })
+    }
+
+    /// Collects the following (or preceding, if `rev` is `true`) sibling elements of each element,
+    /// stopping before the first element matching the matcher.
+    fn siblings_until(&self, matcher: Option<&Matcher>, rev: bool) -> Selection<'a> {
+        let Some(first) = self.nodes().first() else {
+            return Default::default();
+        };
+
+        let mut set = Vec::with_capacity(self.length());
+        let mut caches = Default::default();
+        let tree_nodes = first.tree.nodes.borrow();
+        let next_of = if rev {
+            TreeNodeOps::prev_element_sibling_of
+        } else {
+            TreeNodeOps::next_element_sibling_of
+        };
+
+        for node in self.nodes() {
+            let mut next_id = next_of(tree_nodes.deref(), &node.id);
+            while let Some(id) = next_id {
+                let sibling = NodeRef::new(id, first.tree);
+                if matcher.map_or(false, |m| {
+                    m.match_element_with_caches(&sibling, &mut caches)
+                }) {
+                    break;
+                }
+                if !set.contains(&id) {
+                    set.push(id);
+                }
+                next_id = next_of(tree_nodes.deref(), &id);
+            }
+        }
+
+        let result = set.iter().map(|id| NodeRef::new(*id, first.tree)).collect();
+        Self { nodes: result }
+    }
+
+    /// Collects the ancestor elements of each element, stopping before the first element matching the matcher.
+    fn ancestors_until(&self, matcher: Option<&Matcher>) -> Selection<'a> {
+        let Some(first) = self.nodes().first() else {
+            return Default::default();
+        };
+
+        let mut set = Vec::with_capacity(self.length());
+        let mut caches = Default::default();
+        let tree_nodes = first.tree.nodes.borrow();
+
+        for node in self.nodes() {
+            for ancestor in ancestor_nodes(Ref::clone(&tree_nodes), &node.id, None)
+                .flat_map(|id| tree_nodes.get(id.value))
+            {
+                if !ancestor.is_element() {
+                    continue;
+                }
+                let ancestor_ref = NodeRef::new(ancestor.id, first.tree);
+                if matcher.map_or(false, |m| {
+                    m.match_element_with_caches(&ancestor_ref, &mut caches)
+                }) {
+                    break;
+                }
+                if !set.contains(&ancestor.id) {
+                    set.push(ancestor.id);
+                }
+            }
+        }
+
+        let result = set.iter().map(|id| NodeRef::new(*id, first.tree)).collect();
+        Self { nodes: result }
+    }
+
     fn derive_selection<'b, F>(&self, f: F) -> Selection<'a>
     where
         F: Fn(Ref<Vec<TreeNode>>, &NodeRef<'a>) -> Option<NodeRef<'a>>,
@@ -726,6 +1973,52 @@ impl<'a> Selection<'a> {
         }
     }
 
+    /// Reduces the set of matched elements to the one at the specified index.
+    /// It returns a new selection object, and an empty selection object if the
+    /// index is out of range.
+    pub fn eq(&self, index: usize) -> Selection<'a> {
+        self.nodes
+            .get(index)
+            .map_or_else(Default::default, |node| Selection::from(node.clone()))
+    }
+
+    /// Reduces the set of matched elements to the given range of indices.
+    /// The bounds are clamped to the length of the selection, so an out-of-range
+    /// (or an empty) range results in an empty selection object rather than a panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dom_query::Document;
+    ///
+    /// let doc = Document::from("<ul><li>1</li><li>2</li><li>3</li><li>4</li></ul>");
+    /// let items = doc.select("li");
+    /// assert_eq!(items.slice(1..3).text().as_ref(), "23");
+    /// assert_eq!(items.slice(2..).text().as_ref(), "34");
+    /// assert_eq!(items.slice(..usize::MAX).length(), 4);
+    /// assert!(items.slice(5..).is_empty());
+    /// ```
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Selection<'a> {
+        let len = self.length();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        let end = end.min(len);
+        if start >= end {
+            return Default::default();
+        }
+        Selection {
+            nodes: self.nodes[start..end].to_vec(),
+        }
+    }
+
     /// Retrieves the underlying node at the specified index.
     pub fn get(&self, index: usize) -> Option<&NodeRef<'a>> {
         self.nodes.get(index)
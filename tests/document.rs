@@ -0,0 +1,768 @@
+mod data;
+
+use data::{doc_wiki, ANCESTORS_CONTENTS};
+use dom_query::{Document, DocumentStats, NodeId, TreeNodeOps};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+mod alloc;
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_outline() {
+    let contents = r#"<!DOCTYPE html>
+    <html>
+        <head><title>Test</title></head>
+        <body>
+            <h1>Guide</h1>
+            <div>
+                <h2 id="install"> Installation </h2>
+                <p>Some text</p>
+                <h3>From <em>source</em></h3>
+            </div>
+            <h2>Usage</h2>
+            <h4>Notes</h4>
+            <h6>Footnote</h6>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+
+    let outline = doc.outline();
+    let got: Vec<(u8, &str)> = outline
+        .iter()
+        .map(|(level, text, _)| (*level, text.as_ref()))
+        .collect();
+    assert_eq!(
+        got,
+        vec![
+            (1, "Guide"),
+            (2, "Installation"),
+            (3, "From source"),
+            (2, "Usage"),
+            (4, "Notes"),
+            (6, "Footnote"),
+        ]
+    );
+
+    // node ids point to the heading elements
+    let install_id = outline[1].2;
+    let install = doc.tree.get(&install_id).unwrap();
+    assert_eq!(install.attr("id").unwrap(), "install".into());
+
+    let doc = Document::from("<p>No headings</p>");
+    assert!(doc.outline().is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_meta_refresh() {
+    let doc_with_refresh = |content: &str| {
+        Document::from(format!(
+            r#"<html><head><meta http-equiv="Refresh" content="{}"></head><body></body></html>"#,
+            content
+        ))
+    };
+
+    let doc = doc_with_refresh("5;url=/next");
+    assert_eq!(doc.meta_refresh(), Some((5, Some("/next".into()))));
+
+    let doc = doc_with_refresh("0; URL='https://example.com/?a=1'");
+    assert_eq!(
+        doc.meta_refresh(),
+        Some((0, Some("https://example.com/?a=1".into())))
+    );
+
+    let doc = doc_with_refresh("3, /other");
+    assert_eq!(doc.meta_refresh(), Some((3, Some("/other".into()))));
+
+    // only a delay means the page is reloaded
+    let doc = doc_with_refresh("10");
+    assert_eq!(doc.meta_refresh(), Some((10, None)));
+
+    // malformed values
+    let doc = doc_with_refresh("soon;url=/next");
+    assert_eq!(doc.meta_refresh(), None);
+    let doc = doc_with_refresh("5 url=/next");
+    assert_eq!(doc.meta_refresh(), None);
+    let doc = doc_with_refresh("");
+    assert_eq!(doc.meta_refresh(), None);
+
+    let doc = Document::from(r#"<meta http-equiv="content-type" content="text/html">"#);
+    assert_eq!(doc.meta_refresh(), None);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_language() {
+    let doc = Document::from(
+        r#"<!DOCTYPE html><html lang="en"><head><title>Test</title></head><body></body></html>"#,
+    );
+    assert_eq!(doc.language(), Some("en".into()));
+
+    // `lang` attribute takes precedence over the meta element
+    let doc = Document::from(
+        r#"<html lang="de-AT"><head><meta http-equiv="content-language" content="fr"></head></html>"#,
+    );
+    assert_eq!(doc.language(), Some("de-AT".into()));
+
+    let doc = Document::from(
+        r#"<html><head><meta http-equiv="Content-Language" content=" fr, en "></head></html>"#,
+    );
+    assert_eq!(doc.language(), Some("fr".into()));
+
+    let doc = Document::from(
+        r#"<html lang=""><head><meta http-equiv="content-language" content="es"></head></html>"#,
+    );
+    assert_eq!(doc.language(), Some("es".into()));
+
+    // `lang` of nested elements is not the document language
+    let doc = Document::from(r#"<html><body><p lang="it">Ciao</p></body></html>"#);
+    assert_eq!(doc.language(), None);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_parse_fragment_nodes() {
+    let doc = Document::from(
+        r#"<!DOCTYPE html><html><head></head><body><ul id="list"><li>First</li></ul></body></html>"#,
+    );
+
+    let nodes = doc.parse_fragment_nodes("<li>Second</li> <li class=\"last\">Third</li>");
+    assert_eq!(nodes.len(), 3);
+    assert!(nodes[1].is_whitespace_only());
+    // parsed nodes are detached
+    for node in nodes.iter() {
+        assert!(node.parent().is_none());
+        assert!(node.prev_sibling().is_none());
+        assert!(node.next_sibling().is_none());
+    }
+    assert!(!doc.select("li.last").exists());
+
+    let list_sel = doc.select("#list");
+    let list = list_sel.nodes().first().unwrap();
+    list.append_child(&nodes[2]);
+    list.append_child(&nodes[0]);
+    assert_eq!(
+        list.inner_html(),
+        r#"<li>First</li><li class="last">Third</li><li>Second</li>"#.into()
+    );
+    assert_eq!(doc.select("li.last").length(), 1);
+
+    assert!(doc.parse_fragment_nodes("").is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_remove_empty_elements() {
+    let contents = r#"<!DOCTYPE html>
+    <html>
+        <head><title>Test</title></head>
+        <body>
+            <div id="main">
+                <div class="wrapper">
+                    <div><span></span></div>
+                    <p>
+                    </p>
+                </div>
+                <p>Keep <span></span></p>
+                <div><img src="a.png"></div>
+                <section></section>
+            </div>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+
+    doc.remove_empty_elements(&["div", "p", "span"]);
+
+    // nested empty wrappers are removed, once their children are removed
+    assert!(!doc.select(".wrapper").exists());
+    // an empty span inside a non-empty paragraph is removed, the paragraph stays
+    assert_eq!(doc.select("#main > p").text(), "Keep ".into());
+    assert!(!doc.select("span").exists());
+    // elements with element children are not empty
+    assert!(doc.select("#main > div > img").exists());
+    // elements, that are not in the list, are kept
+    assert!(doc.select("section").exists());
+    // `title` is not in the list
+    assert!(doc.select("title").exists());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_text_nodes() {
+    let doc = Document::from(ANCESTORS_CONTENTS);
+
+    let non_empty: Vec<String> = doc
+        .text_nodes_it()
+        .filter(|n| !n.is_whitespace_only())
+        .map(|n| n.text().to_string())
+        .collect();
+    // the comment is not a text node
+    assert_eq!(non_empty, vec!["Test", "Child", "Child"]);
+
+    let expected_count = doc
+        .root()
+        .descendants()
+        .iter()
+        .filter(|n| n.is_text())
+        .count();
+    assert_eq!(doc.text_nodes_it().count(), expected_count);
+
+    // collected text nodes can be modified
+    for node in doc.text_nodes() {
+        if node.is_whitespace_only() {
+            node.remove_from_parent();
+        }
+    }
+    assert_eq!(doc.text_nodes().len(), 3);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_collect_attrs() {
+    let contents = r#"<!DOCTYPE html>
+    <html>
+        <head><title>Gallery</title></head>
+        <body>
+            <div class="gallery">
+                <img src="/img/1.png" alt="One">
+                <img alt="No source">
+                <img src="" alt="Empty source">
+                <img src="/img/2.png" alt="Two">
+            </div>
+            <img src="/img/logo.png" alt="Logo">
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+
+    let srcs = doc.collect_attrs(".gallery img", "src");
+    assert_eq!(srcs, vec!["/img/1.png".into(), "/img/2.png".into()]);
+
+    let srcs = doc.collect_attrs("img", "src");
+    assert_eq!(srcs.len(), 3);
+    assert_eq!(srcs[2], "/img/logo.png".into());
+
+    assert!(doc.collect_attrs("video", "src").is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_fragment_from_nodes() {
+    let first = Document::from(
+        r#"<html><body><h1 id="title">News</h1><ul><li>One</li><li>Two</li></ul></body></html>"#,
+    );
+    let second = Document::from(
+        r#"<html><body><article><p class="lead">Lead <b>text</b></p></article></body></html>"#,
+    );
+
+    let title = first.select("#title");
+    let lead = second.select(".lead");
+    let items = first.select("li");
+    let mut nodes = vec![];
+    nodes.extend_from_slice(title.nodes());
+    nodes.extend_from_slice(lead.nodes());
+    nodes.extend_from_slice(items.nodes());
+
+    let fragment = Document::fragment_from_nodes(&nodes);
+    assert!(fragment.root().is_fragment());
+    assert_eq!(
+        fragment.select("html").inner_html(),
+        r#"<h1 id="title">News</h1><p class="lead">Lead <b>text</b></p><li>One</li><li>Two</li>"#
+            .into()
+    );
+
+    // the nodes are copied: modifying the fragment doesn't affect the source documents
+    fragment.select("b").remove();
+    assert_eq!(second.select(".lead b").length(), 1);
+    assert_eq!(first.select("li").length(), 2);
+
+    let empty = Document::fragment_from_nodes(&[]);
+    assert_eq!(empty.select("html").inner_html(), "".into());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_text_of_selector() {
+    let contents = r#"<article>
+        <h1>Title</h1>
+        <p>First <b>paragraph</b>.</p>
+        <div><p>Second paragraph.</p></div>
+        <p>Third paragraph.</p>
+    </article>"#;
+    let doc = Document::from(contents);
+
+    let manual = doc
+        .select("p")
+        .iter()
+        .map(|p| p.text().to_string())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let text = doc.text_of_selector("p", "\n\n");
+    assert_eq!(text.as_ref(), manual);
+    assert_eq!(
+        text,
+        "First paragraph.\n\nSecond paragraph.\n\nThird paragraph.".into()
+    );
+
+    assert_eq!(doc.text_of_selector("h1", " | "), "Title".into());
+    assert_eq!(doc.text_of_selector("table", " "), "".into());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_set_doctype() {
+    let doc = Document::from("<html><head><title>Old</title></head><body></body></html>");
+    assert!(!doc.root().first_child().unwrap().is_doctype());
+
+    doc.set_doctype("html", "", "");
+    let first = doc.root().first_child().unwrap();
+    assert!(first.is_doctype());
+    assert_eq!(first.doctype_name().unwrap(), "html".into());
+    assert!(doc.html().starts_with("<!DOCTYPE html><html>"));
+
+    // the existing doctype is replaced
+    let doc = Document::from(
+        r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01//EN" "http://www.w3.org/TR/html4/strict.dtd"><html></html>"#,
+    );
+    doc.set_doctype("html", "", "");
+    let doctypes = doc
+        .root()
+        .children()
+        .into_iter()
+        .filter(|n| n.is_doctype())
+        .count();
+    assert_eq!(doctypes, 1);
+    assert!(doc.html().starts_with("<!DOCTYPE html><html>"));
+
+    let node = doc.tree.new_doctype("svg", "-//W3C//DTD SVG 1.1//EN", "");
+    assert!(node.is_doctype());
+    assert!(node.parent().is_none());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_deduplicate_whitespace() {
+    let contents = "<div id=\"main\">\n    <p>  Some   text\n\twith <b>bold</b>   words  </p>\n    <pre>  keep\n    this  </pre>\n    <textarea>  and\n\n this</textarea>\n</div>";
+    let doc = Document::from(contents);
+    let text_nodes_before = doc.text_nodes().len();
+
+    doc.deduplicate_whitespace();
+
+    // the structure is kept
+    assert_eq!(doc.text_nodes().len(), text_nodes_before);
+    assert_eq!(
+        doc.select("p").html().as_ref(),
+        "<p> Some text with <b>bold</b> words </p>"
+    );
+    // preformatted text is preserved
+    assert_eq!(doc.select("pre").text().as_ref(), "  keep\n    this  ");
+    assert_eq!(doc.select("textarea").text().as_ref(), "  and\n\n this");
+    // whitespace-only text nodes are collapsed too
+    assert!(doc
+        .text_nodes()
+        .iter()
+        .filter(|n| n.is_whitespace_only())
+        .all(|n| n.text().as_ref() == " "));
+}
+
+#[cfg(feature = "url")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_classify_links() {
+    let contents = r#"<!DOCTYPE html>
+    <html>
+        <head><link href="/style.css" rel="stylesheet"></head>
+        <body>
+            <a href="/about">About</a>
+            <a href="posts/1.html">Post</a>
+            <a href="../index.html">Home</a>
+            <a href="https://EXAMPLE.com/contacts">Contacts</a>
+            <a href="//cdn.example.net/file.zip">File</a>
+            <a href="https://github.com/niklak/dom_query">Repo</a>
+            <a href="mailto:info@example.com">Mail</a>
+            <a href="javascript:void(0)">Nothing</a>
+            <a href="http://[::1">Broken</a>
+            <a name="anchor">No href</a>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+    let (internal, external) = doc.classify_links("https://example.com/blog/");
+
+    assert_eq!(
+        internal,
+        vec![
+            "https://example.com/about".into(),
+            "https://example.com/blog/posts/1.html".into(),
+            "https://example.com/index.html".into(),
+            "https://example.com/contacts".into(),
+        ]
+    );
+    assert_eq!(
+        external,
+        vec![
+            "https://cdn.example.net/file.zip".into(),
+            "https://github.com/niklak/dom_query".into(),
+        ]
+    );
+
+    // invalid base
+    let (internal, external) = doc.classify_links("/blog/");
+    assert!(internal.is_empty());
+    assert!(external.is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_json_ld() {
+    let contents = r#"<!DOCTYPE html>
+    <html>
+        <head>
+            <script type="application/ld+json">
+            {"@context": "https://schema.org", "@type": "Product", "name": "Lamp"}
+            </script>
+            <script type="text/javascript">var x = 1;</script>
+        </head>
+        <body>
+            <script>var y = 2;</script>
+            <script type="Application/LD+JSON">{ not json }</script>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+    let blocks = doc.json_ld();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(
+        blocks[0].trim(),
+        r#"{"@context": "https://schema.org", "@type": "Product", "name": "Lamp"}"#
+    );
+    assert_eq!(blocks[1].as_ref(), "{ not json }");
+
+    assert!(Document::from("<p>No data</p>").json_ld().is_empty());
+}
+
+#[cfg(feature = "json")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_json_ld_values() {
+    let contents = r#"<head>
+        <script type="application/ld+json">{"@type": "Product", "name": "Lamp", "offers": {"price": 25}}</script>
+        <script type="application/ld+json">{ not json }</script>
+    </head>"#;
+    let doc = Document::from(contents);
+    let values = doc.json_ld_values();
+    // the invalid block is skipped
+    assert_eq!(values.len(), 1);
+    assert_eq!(values[0]["@type"], "Product");
+    assert_eq!(values[0]["offers"]["price"], 25);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_count() {
+    let doc = doc_wiki();
+    for sel in [
+        "a",
+        "div",
+        "a[href^='/wiki/']",
+        "li > a",
+        "table tr",
+        "p:has(b)",
+    ] {
+        assert_eq!(doc.count(sel), doc.select(sel).length(), "selector: {sel}");
+    }
+    assert!(doc.count("a") > 100);
+    assert_eq!(doc.count("unknown-element"), 0);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_with_nodes_mut() {
+    let doc = Document::from(ANCESTORS_CONTENTS);
+    let div_count = doc.select("div").length();
+
+    // bulk rename and removal under a single borrow
+    let removed = doc.with_nodes_mut(|nodes| {
+        let mut comments: Vec<NodeId> = vec![];
+        for node in nodes.iter_mut() {
+            if node.is_comment() {
+                comments.push(node.id);
+            } else if node
+                .as_element()
+                .map_or(false, |el| el.name.local.as_ref() == "div")
+            {
+                node.rename("section");
+            }
+        }
+        for id in comments.iter() {
+            TreeNodeOps::remove_from_parent(nodes, id);
+        }
+        comments.len()
+    });
+    assert_eq!(removed, 1);
+
+    assert!(!doc.select("div").exists());
+    assert_eq!(doc.select("section").length(), div_count);
+    assert!(!doc.html().contains("<!--"));
+    assert!(doc
+        .select("#grand-parent > #parent > #first-child")
+        .exists());
+
+    // the links between nodes are consistent
+    doc.with_nodes_mut(|nodes| {
+        let get = |id: NodeId| nodes.iter().find(|node| node.id == id).unwrap();
+        let mut stack = vec![doc.tree.root_id()];
+        while let Some(id) = stack.pop() {
+            let node = get(id);
+            let mut prev = None;
+            let mut child_id = node.first_child;
+            while let Some(cid) = child_id {
+                let child = get(cid);
+                assert_eq!(child.parent, Some(id));
+                assert_eq!(child.prev_sibling, prev);
+                stack.push(cid);
+                prev = Some(cid);
+                child_id = child.next_sibling;
+            }
+            assert_eq!(node.last_child, prev);
+        }
+    });
+}
+
+#[cfg(debug_assertions)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[should_panic(expected = "inconsistent links")]
+fn test_doc_with_nodes_mut_broken_links() {
+    let doc = Document::from(ANCESTORS_CONTENTS);
+    doc.with_nodes_mut(|nodes| {
+        // detaching a node from its siblings without updating its parent breaks the child list
+        let node = nodes
+            .iter_mut()
+            .find(|node| node.prev_sibling.is_some() && node.next_sibling.is_some())
+            .unwrap();
+        node.prev_sibling = None;
+    });
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_article_text() {
+    let contents = r#"<!DOCTYPE html>
+    <html>
+        <head><title>News</title><style>p { color: red; }</style></head>
+        <body>
+            <header><a href="/">Logo</a></header>
+            <nav><a href="/">Home</a> | <a href="/about">About</a></nav>
+            <div class="layout">
+                <article>
+                    <header><h1>Big news</h1></header>
+                    <p>The first paragraph with <b>bold</b> text.</p>
+                    <div class="ads-top">Buy our stuff!</div>
+                    <script>trackUser();</script>
+                    <p style="display: none">Hidden teaser.</p>
+                    <p>The second paragraph.</p>
+                    <div id="banner_bottom">Subscribe!</div>
+                    <footer>Share this article</footer>
+                </article>
+                <aside>Related links</aside>
+            </div>
+            <footer>Copyright</footer>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+    let text = doc.article_text();
+    assert_eq!(
+        text.as_ref(),
+        "Big news\n\nThe first paragraph with bold text.\n\nThe second paragraph."
+    );
+    for excluded in [
+        "Logo",
+        "Home",
+        "Buy",
+        "trackUser",
+        "Hidden",
+        "Subscribe",
+        "Share",
+        "Related",
+        "Copyright",
+    ] {
+        assert!(!text.contains(excluded), "{excluded}");
+    }
+    // the document is not changed
+    assert!(doc.select("nav").exists());
+    assert!(doc.select(".ads-top").exists());
+
+    // without an article the whole body is used
+    let doc = Document::from(
+        r#"<body><header>Site</header><div class="content"><p>Text</p><p class="loading">Loading</p></div></body>"#,
+    );
+    assert_eq!(doc.article_text().as_ref(), "Text\n\nLoading");
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_prune() {
+    let contents = r#"<!DOCTYPE html>
+    <html>
+        <head><title>Page</title></head>
+        <body>
+            <nav><a href="/">Home</a><div class="ad">Nested ad</div></nav>
+            <main>
+                <p>First</p>
+                <div class="ad">Ad 1</div>
+                <p>Second</p>
+                <div class="ad banner">Ad 2</div>
+            </main>
+            <footer>Footer</footer>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+
+    assert_eq!(doc.prune(".ad, nav, footer"), 5);
+    assert!(!doc.select(".ad, nav, footer").exists());
+    assert_eq!(
+        doc.select("main")
+            .text()
+            .split_whitespace()
+            .collect::<Vec<_>>(),
+        vec!["First", "Second"]
+    );
+    assert_eq!(doc.select("body > *").length(), 1);
+
+    // nothing to prune
+    assert_eq!(doc.prune(".ad"), 0);
+    assert_eq!(doc.select("main > p").length(), 2);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_select_tags() {
+    let contents = r#"<body>
+        <h1>Title</h1>
+        <section><h2>Chapter</h2><p>Text</p><h3>Part</h3></section>
+        <header><h6>Small</h6></header>
+        <hr>
+        <h2>Another chapter</h2>
+    </body>"#;
+    let doc = Document::from(contents);
+
+    let headings = doc.select_tags(&["h1", "h2", "h3", "h4", "h5", "h6"]);
+    let texts: Vec<_> = headings.iter().map(|h| h.text().to_string()).collect();
+    // document order
+    assert_eq!(
+        texts,
+        vec!["Title", "Chapter", "Part", "Small", "Another chapter"]
+    );
+    assert!(headings.same_nodes_as(&doc.select("h1, h2, h3, h4, h5, h6")));
+
+    let by_predicate = doc.select_by_tag(|name| {
+        name.len() == 2 && name.starts_with('h') && name[1..].parse::<u8>().is_ok()
+    });
+    assert!(by_predicate.same_nodes_as(&headings));
+
+    assert!(doc.select_tags(&["table"]).is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_fix_encoding_entities() {
+    let contents = r#"<body>
+        <p id="double">Fish &amp;amp; Chips &amp;#38; &amp;#x3C;b&amp;gt;</p>
+        <p id="single">Fish &amp; Chips &lt;b&gt; &amp;unknown; &amp;amp AT&amp;T</p>
+        <script>if (a &amp;amp; b) {}</script>
+    </body>"#;
+    let doc = Document::from(contents);
+    doc.fix_encoding_entities();
+
+    let double = doc.select("#double");
+    assert_eq!(double.text().as_ref(), "Fish & Chips & <b>");
+    assert_eq!(
+        double.inner_html().as_ref(),
+        "Fish &amp; Chips &amp; &lt;b&gt;"
+    );
+
+    // single-encoded text, unknown and unterminated entities are left alone
+    let single = doc.select("#single");
+    assert_eq!(
+        single.text().as_ref(),
+        "Fish & Chips <b> &unknown; &amp AT&T"
+    );
+
+    // raw text of scripts is never decoded
+    assert_eq!(
+        doc.select("script").text().as_ref(),
+        "if (a &amp;amp; b) {}"
+    );
+}
+
+#[cfg(feature = "url")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_resolve_url() {
+    let contents = r#"<html>
+        <head>
+            <base href="https://example.com/blog/2024/">
+            <link rel="canonical" href="https://other.com/page">
+        </head>
+        <body></body>
+    </html>"#;
+    let doc = Document::from(contents);
+
+    let cases = [
+        ("post.html", "https://example.com/blog/2024/post.html"),
+        ("./post.html", "https://example.com/blog/2024/post.html"),
+        ("../2023/old.html", "https://example.com/blog/2023/old.html"),
+        ("/about", "https://example.com/about"),
+        ("//cdn.example.com/app.js", "https://cdn.example.com/app.js"),
+        ("?page=2", "https://example.com/blog/2024/?page=2"),
+        ("#top", "https://example.com/blog/2024/#top"),
+        (" post.html ", "https://example.com/blog/2024/post.html"),
+        ("https://example.org/x", "https://example.org/x"),
+        ("mailto:me@example.com", "mailto:me@example.com"),
+    ];
+    for (href, expected) in cases {
+        assert_eq!(doc.resolve_url(href), Some(expected.into()), "{}", href);
+    }
+
+    // the canonical url is used when there is no `<base>`
+    let doc = Document::from(
+        r#"<head><link rel="canonical" href="https://example.com/docs/intro"></head>"#,
+    );
+    assert_eq!(
+        doc.resolve_url("setup"),
+        Some("https://example.com/docs/setup".into())
+    );
+
+    // a relative `<base>` is not a suitable base url
+    let doc = Document::from(r#"<head><base href="/relative/"></head>"#);
+    assert_eq!(doc.resolve_url("page.html"), None);
+    assert_eq!(
+        doc.resolve_url("https://example.com/"),
+        Some("https://example.com/".into())
+    );
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_doc_stats() {
+    let contents = r#"<html><head><title>Stats</title><script>var x = 1;</script></head><body><!-- top --><h1>Hello,  world</h1><p>See <a href="/one">one</a> and <a name="anchor">two</a>.</p><img src="a.png"><img src="b.png" alt="b"><!-- bottom --></body></html>"#;
+    let doc = Document::from(contents);
+
+    let stats = doc.stats();
+    assert_eq!(
+        stats,
+        DocumentStats {
+            // html, head, title, script, body, h1, p, a, a, img, img
+            elements: 11,
+            // "Stats", "var x = 1;", "Hello,  world", "See ", "one", " and ", "two", "."
+            text_nodes: 8,
+            comments: 2,
+            links: 1,
+            images: 2,
+            // the script text is not counted, double spaces are counted once
+            text_length: "Stats".len() + "Hello, world".len() + "See one and two.".len(),
+        }
+    );
+
+    assert_eq!(Document::from("").stats().text_nodes, 0);
+}
@@ -1,7 +1,7 @@
 mod data;
 
 use data::{ANCESTORS_CONTENTS, REPLACEMENT_CONTENTS};
-use dom_query::Document;
+use dom_query::{Document, InsertPosition};
 
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen_test::*;
@@ -671,3 +671,317 @@ fn test_node_rename() {
     assert_eq!(doc.select("#parent div").length(), 1);
     assert_eq!(doc.select("#parent p").length(), 1);
 }
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_set_text_escaped() {
+    let doc = Document::from(REPLACEMENT_CONTENTS);
+    let sel = doc.select("#main");
+    let node = sel.nodes().first().unwrap();
+
+    node.set_text("<b>");
+    assert_eq!(node.inner_html(), "&lt;b&gt;".into());
+    assert_eq!(node.text(), "<b>".into());
+    assert!(doc.select("#main b").is_empty());
+
+    // line breaks are kept in the text node as is
+    node.set_text("first\nsecond & third");
+    assert_eq!(node.inner_html(), "first\nsecond &amp; third".into());
+    assert_eq!(node.children().len(), 1);
+    assert!(doc.select("#main br").is_empty());
+
+    node.set_text("");
+    assert_eq!(node.text(), "".into());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_replace_with_text() {
+    let doc = Document::from(REPLACEMENT_CONTENTS);
+    let sel = doc.select("#origin #inline");
+    let node = sel.nodes().first().unwrap();
+
+    node.replace_with_text("<b>1 & 2</b>");
+
+    assert!(!doc.select("#inline").exists());
+    // the text is inserted as a text node, not as markup
+    assert!(!doc.select("#origin b").exists());
+    let origin_sel = doc.select("#origin");
+    let origin = origin_sel.nodes().first().unwrap();
+    let children = origin.children();
+    assert_eq!(children.len(), 1);
+    assert!(children[0].is_text());
+    assert_eq!(children[0].text(), "<b>1 & 2</b>".into());
+    assert_eq!(origin.inner_html(), "&lt;b&gt;1 &amp; 2&lt;/b&gt;".into());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_wrap_text_nodes() {
+    let doc = Document::from(
+        r#"<div id="main">
+    First <b>bold</b> middle <i>italic <u>deep</u></i>
+    <!--comment-->last</div>"#,
+    );
+    let sel = doc.select("#main");
+    let node = sel.nodes().first().unwrap();
+
+    node.wrap_text_nodes("span");
+
+    let spans: Vec<_> = doc
+        .select("#main > span")
+        .iter()
+        .map(|s| s.text().trim().to_string())
+        .collect();
+    assert_eq!(spans, vec!["First", "middle", "last"]);
+    // only direct text children are wrapped
+    assert!(!doc.select("#main i span").exists());
+    assert!(!doc.select("#main b span").exists());
+    // whitespace-only text nodes are left as is
+    let children = node.children();
+    assert!(children.iter().any(|n| n.is_whitespace_only()));
+    assert!(children
+        .iter()
+        .all(|n| !n.is_text() || n.is_whitespace_only()));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_append_prepend_selection() {
+    let doc = Document::from(
+        r#"<ul id="target"><li>Middle</li></ul>
+        <div id="source"><li class="a">A</li><li class="b">B <b>bold</b></li></div>"#,
+    );
+    let target_sel = doc.select("#target");
+    let target = target_sel.nodes().first().unwrap();
+
+    target.append_selection(&doc.select("#source li"));
+    assert_eq!(
+        target.inner_html(),
+        r#"<li>Middle</li><li class="a">A</li><li class="b">B <b>bold</b></li>"#.into()
+    );
+    // original nodes are removed from their parents
+    assert_eq!(doc.select("#source").children().length(), 0);
+
+    // a selection from another document is copied as well
+    let other = Document::from(r#"<p><li class="x">X</li><li class="y">Y</li></p>"#);
+    target.prepend_selection(&other.select("li"));
+    let classes: Vec<_> = target
+        .element_children()
+        .iter()
+        .map(|n| n.attr_or("class", ""))
+        .collect();
+    assert_eq!(
+        classes,
+        vec!["x".into(), "y".into(), "".into(), "a".into(), "b".into()]
+    );
+    assert!(!other.select("p li").exists());
+
+    // an empty selection changes nothing
+    target.append_selection(&doc.select("#missing"));
+    assert_eq!(target.element_children().len(), 5);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_create_comment() {
+    let doc = Document::from(REPLACEMENT_CONTENTS);
+    let sel = doc.select_single("#origin");
+    let origin = sel.nodes().first().unwrap();
+
+    let start = doc.tree.new_comment(" start of origin ");
+    assert!(start.is_comment());
+    assert!(start.parent().is_none());
+    assert_eq!(start.comment_text().unwrap(), " start of origin ".into());
+
+    origin.insert_before(&start);
+    let end = doc.tree.new_comment("end");
+    origin.insert_after(&end);
+
+    let main_html = doc.select("#main").html();
+    assert!(main_html.contains(
+        r#"<!-- start of origin --><p id="origin"><span id="inline">Something</span></p><!--end-->"#
+    ));
+    // comments are not elements and have no text
+    assert_eq!(doc.select("#main").children().length(), 3);
+    assert!(!doc.select("#main").text().contains("start"));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_move_to() {
+    let contents = r#"<div id="src"><span id="moving">M</span></div>
+    <ul id="list"><li id="a">A</li><li id="b">B</li><li id="c">C</li></ul>"#;
+    let doc = Document::from(contents);
+    let list_sel = doc.select_single("#list");
+    let list = list_sel.nodes().first().unwrap();
+    let moving_sel = doc.select_single("#moving");
+    let moving = moving_sel.nodes().first().unwrap();
+    let b_sel = doc.select_single("#b");
+    let b = b_sel.nodes().first().unwrap();
+
+    let child_ids = || -> Vec<String> {
+        list.element_children()
+            .iter()
+            .map(|n| n.attr("id").unwrap().to_string())
+            .collect()
+    };
+
+    moving.move_to(list, InsertPosition::First);
+    assert!(!doc.select("#src").children().exists());
+    assert_eq!(child_ids(), vec!["moving", "a", "b", "c"]);
+
+    moving.move_to(list, InsertPosition::Last);
+    assert_eq!(child_ids(), vec!["a", "b", "c", "moving"]);
+
+    moving.move_to(list, InsertPosition::Before(b.clone()));
+    assert_eq!(child_ids(), vec!["a", "moving", "b", "c"]);
+
+    moving.move_to(list, InsertPosition::After(b.clone()));
+    assert_eq!(child_ids(), vec!["a", "b", "moving", "c"]);
+    assert_eq!(moving.parent().unwrap().id, list.id);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_move_to_invalid() {
+    let doc = Document::from(ANCESTORS_CONTENTS);
+    let html_before = doc.html();
+
+    let child_sel = doc.select_single("#first-child");
+    let child = child_sel.nodes().first().unwrap();
+    let grand_parent_sel = doc.select_single("#grand-parent");
+    let grand_parent = grand_parent_sel.nodes().first().unwrap();
+
+    // a node can't be moved into its descendant or itself
+    grand_parent.move_to(child, InsertPosition::Last);
+    grand_parent.move_to(grand_parent, InsertPosition::First);
+    // the sibling must be a child of the new parent
+    child.move_to(grand_parent, InsertPosition::Before(child.clone()));
+    child.move_to(grand_parent, InsertPosition::After(child.clone()));
+
+    assert_eq!(doc.html(), html_before);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_replace_children() {
+    let doc = Document::from(r#"<div id="main"><p>Old</p><p>Content</p></div>"#);
+    let sel = doc.select("#main");
+    let main = sel.nodes().first().unwrap();
+
+    // html string
+    main.replace_children(r#"<span class="new">New</span>"#);
+    assert_eq!(
+        main.inner_html().as_ref(),
+        r#"<span class="new">New</span>"#
+    );
+    main.replace_children(String::from("<b>Owned</b>"));
+    assert_eq!(main.inner_html().as_ref(), "<b>Owned</b>");
+
+    // selection from another document
+    let other = Document::from(r#"<ul><li>1</li><li>2</li></ul>"#);
+    let items = other.select("li");
+    main.replace_children(&items);
+    assert_eq!(main.inner_html().as_ref(), "<li>1</li><li>2</li>");
+    // like `append_selection`, the original nodes are removed
+    assert!(!other.select("li").exists());
+
+    // selection from the same document
+    let doc =
+        Document::from(r#"<div id="main"><p>Old</p></div><section><i>A</i><i>B</i></section>"#);
+    let sel = doc.select("#main");
+    let main = sel.nodes().first().unwrap();
+    main.set_children_from_selection(&doc.select("section i"));
+    assert_eq!(main.inner_html().as_ref(), "<i>A</i><i>B</i>");
+    assert_eq!(doc.select("section").inner_html().as_ref(), "");
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_replace_tag() {
+    let contents =
+        r#"<div id="main"><font color="red" size="3">Warning: <b>read</b> this</font></div>"#;
+    let doc = Document::from(contents);
+    let font_sel = doc.select("font");
+    let font = font_sel.nodes().first().unwrap();
+    let color = font.attr("color").unwrap();
+
+    font.replace_tag("span", &[("style", &format!("color:{}", color))]);
+
+    assert!(!doc.select("font").exists());
+    assert_eq!(
+        doc.select("#main").inner_html().as_ref(),
+        r#"<span style="color:red">Warning: <b>read</b> this</span>"#
+    );
+    // the node keeps its place in the tree
+    assert!(doc.select("#main > span > b").exists());
+    assert_eq!(font.attrs().len(), 1);
+
+    // only elements are changed
+    let text = doc.select("b").nodes()[0].first_child().unwrap();
+    text.replace_tag("i", &[("class", "x")]);
+    assert_eq!(text.text().as_ref(), "read");
+    assert!(!doc.select("i").exists());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_wrap_if_inline() {
+    let contents = r#"<div id="main">Loose text<span>inline</span><div>block</div><!-- comment --><ul><li>item</li></ul><img src="a.png"></div>"#;
+    let doc = Document::from(contents);
+    let main_sel = doc.select("#main");
+    let main = main_sel.nodes().first().unwrap();
+
+    let wrapped: Vec<bool> = main
+        .children()
+        .iter()
+        .map(|child| child.wrap_if_inline("p"))
+        .collect();
+    assert_eq!(wrapped, vec![true, true, false, false, false, true]);
+    assert_eq!(
+        main.inner_html().as_ref(),
+        r#"<p>Loose text</p><p><span>inline</span></p><div>block</div><!-- comment --><ul><li>item</li></ul><p><img src="a.png"></p>"#
+    );
+
+    // a list item is not inline
+    let li_sel = doc.select("li");
+    let li = li_sel.nodes().first().unwrap();
+    assert!(!li.wrap_if_inline("p"));
+    assert!(doc.select("ul > li").exists());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_replace_with_children() {
+    let contents = r#"<p id="main">Start <span class="wrapper"><b>bold</b> and <i>italic</i></span> end<span class="empty"></span></p>"#;
+    let doc = Document::from(contents);
+
+    let sel = doc.select(".wrapper");
+    let wrapper = sel.nodes().first().unwrap();
+    wrapper.replace_with_children();
+    assert!(wrapper.parent().is_none());
+    assert_eq!(
+        doc.select("#main").inner_html().as_ref(),
+        r#"Start <b>bold</b> and <i>italic</i> end<span class="empty"></span>"#
+    );
+
+    // an element without children is simply removed
+    let sel = doc.select(".empty");
+    let empty = sel.nodes().first().unwrap();
+    empty.replace_with_children();
+    assert_eq!(
+        doc.select("#main").inner_html().as_ref(),
+        "Start <b>bold</b> and <i>italic</i> end"
+    );
+
+    // a detached node keeps its children
+    let sel = doc.select("#main");
+    let main = sel.nodes().first().unwrap();
+    main.remove_from_parent();
+    main.replace_with_children();
+    assert_eq!(
+        main.inner_html().as_ref(),
+        "Start <b>bold</b> and <i>italic</i> end"
+    );
+}
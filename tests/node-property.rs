@@ -0,0 +1,347 @@
+use dom_query::{Dir, Document};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::*;
+
+mod alloc;
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_serialize_text_with_links() {
+    let contents = r#"<!DOCTYPE html>
+    <html>
+        <head><title>Test</title></head>
+        <body>
+            <p id="text">
+                To read the docs,   <a href="https://docs.rs/dom_query">click here</a>.
+                The source is <a href="https://github.com/niklak/dom_query">on GitHub</a>,
+                and this <a>anchor</a> has no target.
+            </p>
+            <ul>
+                <li><a href="/first">First</a></li>
+                <li>Second<br>line</li>
+            </ul>
+            <script>let x = 1;</script>
+        </body>
+    </html>"#;
+
+    let doc = Document::from(contents);
+    let sel = doc.select_single("#text");
+    let p = sel.nodes().first().unwrap();
+
+    let expected = "To read the docs, click here (https://docs.rs/dom_query). \
+    The source is on GitHub (https://github.com/niklak/dom_query), \
+    and this anchor has no target.";
+    assert_eq!(p.serialize_text_with_links().as_ref(), expected);
+
+    let body_sel = doc.select_single("body");
+    let body = body_sel.nodes().first().unwrap();
+    let text = body.serialize_text_with_links();
+    assert!(text.ends_with("has no target.\n\nFirst (/first)\nSecond\nline"));
+    assert!(!text.contains("let x"));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_serialize_text_with_links_fn() {
+    let contents =
+        r#"<p>Visit <a href="https://example.com">our site</a> or <a href="">nothing</a>.</p>"#;
+    let doc = Document::from(contents);
+
+    let text = doc
+        .root()
+        .serialize_text_with_links_fn(|href| format!("[{}]", href));
+    assert_eq!(
+        text.as_ref(),
+        "Visit our site[https://example.com] or nothing."
+    );
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_is_whitespace_only() {
+    let doc = Document::from("<div id=\"main\">\n  <p>Text</p></div>");
+    let sel = doc.select_single("#main");
+    let main = sel.nodes().first().unwrap();
+
+    let formatting = main.first_child().unwrap();
+    assert!(formatting.is_text());
+    assert_eq!(formatting.text(), "\n  ".into());
+    assert!(formatting.is_whitespace_only());
+
+    let p = formatting.next_sibling().unwrap();
+    assert!(!p.is_whitespace_only());
+
+    let text = p.first_child().unwrap();
+    assert!(text.is_text());
+    assert!(!text.is_whitespace_only());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_attr_or_default_from_ancestors() {
+    let contents = r#"<html lang="en">
+        <body>
+            <section dir="rtl">
+                <div>
+                    <p id="inherits">text</p>
+                    <p id="own" dir="ltr" lang="fr">texte</p>
+                </div>
+            </section>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+
+    let sel = doc.select("#inherits");
+    let node = sel.nodes().first().unwrap();
+    // `dir` is set on the grandparent
+    assert_eq!(
+        node.attr_or_default_from_ancestors("dir"),
+        Some("rtl".into())
+    );
+    assert_eq!(
+        node.attr_or_default_from_ancestors("lang"),
+        Some("en".into())
+    );
+    assert_eq!(node.attr_or_default_from_ancestors("translate"), None);
+
+    // the node's own attribute takes precedence
+    let sel = doc.select("#own");
+    let node = sel.nodes().first().unwrap();
+    assert_eq!(
+        node.attr_or_default_from_ancestors("dir"),
+        Some("ltr".into())
+    );
+    assert_eq!(
+        node.attr_or_default_from_ancestors("lang"),
+        Some("fr".into())
+    );
+
+    // text nodes inherit attributes of their parent elements
+    let text = node.first_child().unwrap();
+    assert_eq!(
+        text.attr_or_default_from_ancestors("lang"),
+        Some("fr".into())
+    );
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_text_direction() {
+    let contents = r#"<html>
+        <body>
+            <p id="default">text</p>
+            <section dir="RTL">
+                <div>
+                    <p id="inherits">نص</p>
+                    <p id="own" dir="ltr">text</p>
+                    <p id="auto" dir="auto">text</p>
+                </div>
+            </section>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+
+    let direction = |sel: &str| doc.select(sel).nodes().first().unwrap().text_direction();
+    // no `dir` attribute
+    assert_eq!(direction("#default"), Dir::Ltr);
+    // `dir` is set on the grandparent
+    assert_eq!(direction("#inherits"), Dir::Rtl);
+    assert_eq!(direction("section"), Dir::Rtl);
+    // the node's own attribute takes precedence
+    assert_eq!(direction("#own"), Dir::Ltr);
+    assert_eq!(direction("#auto"), Dir::Auto);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_html_escaped_text() {
+    let contents =
+        r#"<div id="main">if a &lt; b &amp;&amp; c &gt; d: <code>print("it's")</code></div>"#;
+    let doc = Document::from(contents);
+    let sel = doc.select_single("#main");
+    let node = sel.nodes().first().unwrap();
+
+    assert_eq!(node.text(), r#"if a < b && c > d: print("it's")"#.into());
+    assert_eq!(
+        node.html_escaped_text(),
+        "if a &lt; b &amp;&amp; c &gt; d: print(&quot;it&#39;s&quot;)".into()
+    );
+
+    // text that is set with `set_text` is read back escaped
+    node.set_text("<b>\"bold\"</b>");
+    assert_eq!(
+        node.html_escaped_text(),
+        "&lt;b&gt;&quot;bold&quot;&lt;/b&gt;".into()
+    );
+
+    let doc = Document::from("<p>plain text</p>");
+    let sel = doc.select_single("p");
+    let node = sel.nodes().first().unwrap();
+    assert_eq!(node.html_escaped_text(), "plain text".into());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_is_visible() {
+    let contents = r#"<html>
+        <head><title>Test</title></head>
+        <body>
+            <p id="plain">Visible</p>
+            <div style="color: red; DISPLAY : None !important"><p id="display-none">text</p></div>
+            <div style="visibility:hidden"><span id="visibility-hidden">text</span></div>
+            <section hidden><p id="hidden-attr">text</p></section>
+            <p id="display-block" style="display: block; visibility: visible">text</p>
+            <script id="script">var x = 1;</script>
+            <template><p id="in-template">text</p></template>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+    let is_visible = |sel: &str| doc.select(sel).nodes().first().unwrap().is_visible();
+
+    assert!(is_visible("#plain"));
+    assert!(is_visible("#display-block"));
+    assert!(is_visible("body"));
+
+    assert!(!is_visible("#display-none"));
+    assert!(!is_visible("#visibility-hidden"));
+    assert!(!is_visible("#hidden-attr"));
+    assert!(!is_visible("#script"));
+    assert!(!is_visible("title"));
+
+    // text nodes inherit visibility from their ancestors
+    let sel = doc.select("#display-none");
+    let text_node = sel.nodes().first().unwrap().first_child().unwrap();
+    assert!(text_node.is_text());
+    assert!(!text_node.is_visible());
+    let sel = doc.select("#plain");
+    assert!(sel
+        .nodes()
+        .first()
+        .unwrap()
+        .first_child()
+        .unwrap()
+        .is_visible());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_outer_html_pretty() {
+    let contents = r#"<div id="main" class="card">
+        <h2>Title</h2>
+        <!-- note -->
+        <p>Some <b>bold</b> text<br></p>
+        <pre>  keep
+  this</pre>
+        <ul><li>One</li><li></li></ul>
+    </div>"#;
+    let doc = Document::from(contents);
+    let sel = doc.select("#main");
+    let main = sel.nodes().first().unwrap();
+
+    let expected = r#"<div id="main" class="card">
+    <h2>
+        Title
+    </h2>
+    <!-- note -->
+    <p>
+        Some
+        <b>
+            bold
+        </b>
+        text
+        <br>
+    </p>
+    <pre>  keep
+  this</pre>
+    <ul>
+        <li>
+            One
+        </li>
+        <li></li>
+    </ul>
+</div>"#;
+    assert_eq!(main.outer_html_pretty(4).as_ref(), expected);
+
+    // the tree is not changed
+    assert!(main.html().contains("<p>Some <b>bold</b> text<br></p>"));
+
+    let sel = doc.select("h2");
+    let h2 = sel.nodes().first().unwrap();
+    assert_eq!(h2.outer_html_pretty(0).as_ref(), "<h2>\nTitle\n</h2>");
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_serialize_children_to() {
+    let contents = r#"<div id="main" class="card"><!-- note --><h2>Title &amp; more</h2><p>Some <b>bold</b> text<br></p>tail</div>"#;
+    let doc = Document::from(contents);
+    let sel = doc.select("#main");
+    let main = sel.nodes().first().unwrap();
+
+    let mut buf: Vec<u8> = vec![];
+    main.serialize_children_to(&mut buf).unwrap();
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        main.inner_html().to_string()
+    );
+
+    let mut buf: Vec<u8> = vec![];
+    main.serialize_to(&mut buf).unwrap();
+    assert_eq!(String::from_utf8(buf).unwrap(), main.html().to_string());
+
+    // an element without children
+    let sel = doc.select("br");
+    let br = sel.nodes().first().unwrap();
+    let mut buf: Vec<u8> = vec![];
+    br.serialize_children_to(&mut buf).unwrap();
+    assert!(buf.is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_has_name() {
+    let contents = r#"<div id="main"><svg><clipPath id="clip"></clipPath></svg>text</div>"#;
+    let doc = Document::from(contents);
+
+    let sel = doc.select("#clip");
+    let clip_path = sel.nodes().first().unwrap();
+    assert_eq!(clip_path.node_name().unwrap(), "clipPath".into());
+
+    assert!(clip_path.has_name("clipPath"));
+    assert!(!clip_path.has_name("clippath"));
+    assert!(clip_path.has_name_ignore_ascii_case("clippath"));
+    assert!(clip_path.has_name_ignore_ascii_case("CLIPPATH"));
+    assert!(!clip_path.has_name_ignore_ascii_case("clip"));
+
+    let main = doc.select("#main");
+    let main = main.nodes().first().unwrap();
+    assert!(main.has_name("div"));
+    assert!(main.has_name_ignore_ascii_case("DIV"));
+
+    // non-element nodes never match
+    let text = main.last_child().unwrap();
+    assert!(text.is_text());
+    assert!(!text.has_name("text"));
+    assert!(!text.has_name_ignore_ascii_case("#text"));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_text_with_alt() {
+    let contents = r#"<p id="main">Press <img src="save.png" alt="Save"> to keep <b>the <img src="doc.png" alt="document"></b>.<img src="spacer.png"></p>"#;
+    let doc = Document::from(contents);
+    let sel = doc.select("#main");
+    let main = sel.nodes().first().unwrap();
+
+    assert_eq!(main.text().as_ref(), "Press  to keep the .");
+    assert_eq!(
+        main.text_with_alt().as_ref(),
+        "Press Save to keep the document."
+    );
+
+    // the image itself
+    let sel = doc.select("img");
+    let img = sel.nodes().first().unwrap();
+    assert_eq!(img.text_with_alt().as_ref(), "Save");
+}
@@ -130,6 +130,19 @@ fn test_descendants() {
     assert_eq!(descendants_id_names, expected_id_names);
 }
 
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_descendants_stay_in_subtree() {
+    let doc: Document = r#"<div id="a"><p>Inner</p></div><div id="b">Outer</div>"#.into();
+
+    let sel = doc.select("#a");
+    let node = sel.nodes().first().unwrap();
+    // descendants of `#a` don't include its following siblings and their descendants
+    let descendants = node.descendants();
+    assert_eq!(descendants.len(), 2);
+    assert!(descendants.iter().all(|n| n.text() == "Inner".into()));
+}
+
 #[cfg_attr(not(target_arch = "wasm32"), test)]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
 fn test_last_child() {
@@ -177,6 +190,31 @@ fn test_is_comment() {
     });
 
     assert_eq!(comment, "Ancestors");
+    assert_eq!(first_comment.comment_text().unwrap(), "Ancestors".into());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_type_accessors() {
+    let doc: Document = ANCESTORS_CONTENTS.into();
+    let root = doc.root();
+
+    let doctype = root.first_child().unwrap();
+    assert!(doctype.is_doctype());
+    assert_eq!(doctype.doctype_name().unwrap(), "html".into());
+    assert!(doctype.comment_text().is_none());
+    assert!(doctype.pi_target().is_none());
+
+    let body_sel = doc.select_single("body");
+    let body = body_sel.nodes().first().unwrap();
+    assert!(body.comment_text().is_none());
+    assert!(body.doctype_name().is_none());
+
+    let comments: Vec<_> = body
+        .children_it(false)
+        .filter_map(|n| n.comment_text())
+        .collect();
+    assert_eq!(comments, vec!["Ancestors".into()]);
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), test)]
@@ -299,4 +337,329 @@ fn test_node_find() {
     assert_eq!(len_fin_ne, 0);
     let len_sel_ne = doc.select("body td p").length();
     assert_eq!(len_sel_ne, 0)
-}
\ No newline at end of file
+}
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_find_wildcard_and_attr() {
+    let contents = r#"<!DOCTYPE html>
+    <html>
+        <head><title>Test</title></head>
+        <body>
+            <div id="main">
+                <p><a href="/first">First</a><a name="anchor">Anchor</a></p>
+                <section><a href="/second">Second</a></section>
+                <a href="/direct">Direct</a>
+            </div>
+            <div><span id="no-class">No class</span><span id="with-class" class="x">Class</span></div>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+
+    // `*` matches any element on that step, `[@href]` requires the attribute.
+    let links: Vec<_> = doc
+        .find(&["body", "div", "*", "a[@href]"])
+        .iter()
+        .map(|n| n.text())
+        .collect();
+    assert_eq!(links, vec!["First".into(), "Second".into()]);
+
+    // without a wildcard step the search goes through any depth, as before.
+    let all_links = doc.find(&["body", "div", "a[@href]"]);
+    assert_eq!(all_links.len(), 3);
+    let named = doc.find(&["body", "div", "a[@name]"]);
+    assert_eq!(named.len(), 1);
+    assert_eq!(named[0].text(), "Anchor".into());
+
+    // an attribute predicate on a wildcard step.
+    let with_class = doc.find(&["body", "*[@class]"]);
+    assert_eq!(with_class.len(), 1);
+    assert_eq!(with_class[0].attr("id").unwrap(), "with-class".into());
+
+    // a malformed predicate matches nothing.
+    assert!(doc.find(&["body", "a[@href"]).is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_nth_element_child() {
+    let doc: Document = r#"<div class="main">
+        Text
+        <span>0</span>
+        <!-- comment -->
+        <p>1</p>
+        <div>2</div>
+        <p>3</p>
+    </div>"#
+        .into();
+    let sel = doc.select_single("div.main");
+    let main_node = sel.nodes().first().unwrap();
+
+    // text and comment nodes are not counted
+    let second = main_node.nth_element_child(1).unwrap();
+    assert_eq!(second.node_name().unwrap(), "p".into());
+    assert_eq!(second.text(), "1".into());
+    assert!(main_node.nth_element_child(4).is_none());
+
+    let first_p = main_node.nth_of_type("p", 0).unwrap();
+    assert_eq!(first_p.text(), "1".into());
+    let second_p = main_node.nth_of_type("p", 1).unwrap();
+    assert_eq!(second_p.text(), "3".into());
+    assert!(main_node.nth_of_type("p", 2).is_none());
+    assert!(main_node.nth_of_type("h1", 0).is_none());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_ancestors_while() {
+    let doc = Document::from(
+        r#"<div id="wrapper" class="section">
+            <div id="inner" class="section">
+                <div id="parent"><p id="target">Text</p></div>
+            </div>
+        </div>"#,
+    );
+    let sel = doc.select_single("#target");
+    let node = sel.nodes().first().unwrap();
+
+    // stops at the first ancestor with the class, excluding it
+    let ids: Vec<_> = node
+        .ancestors_while(|n| !n.has_class("section"))
+        .filter_map(|n| n.attr("id"))
+        .collect();
+    assert_eq!(ids, vec!["parent".into()]);
+
+    // a predicate that never fails yields all ancestors
+    let all = node.ancestors_while(|_| true).count();
+    assert_eq!(all, node.ancestors(None).len());
+
+    // a predicate that fails immediately yields nothing
+    assert_eq!(node.ancestors_while(|n| !n.is_element()).count(), 0);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_table_to_records() {
+    let contents = r#"<table id="people">
+        <thead>
+            <tr><th> Name </th><th>Age</th><th></th></tr>
+        </thead>
+        <tbody>
+            <tr><td>Alice</td><td>30</td><td>admin</td></tr>
+            <tr>
+                <td>Bob</td>
+                <td><table><tr><td>nested</td></tr></table></td>
+            </tr>
+        </tbody>
+    </table>"#;
+    let doc = Document::from(contents);
+
+    let sel = doc.select("#people tbody td");
+    let cell = sel.nodes().first().unwrap();
+    let table = cell.closest_table().unwrap();
+    assert_eq!(table.attr("id").unwrap(), "people".into());
+    // a table is the closest table for itself
+    assert_eq!(table.closest_table().unwrap().id, table.id);
+
+    let records = table.table_to_records();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].len(), 3);
+    assert_eq!(records[0]["Name"], "Alice".into());
+    assert_eq!(records[0]["Age"], "30".into());
+    assert_eq!(records[0]["2"], "admin".into());
+    assert_eq!(records[1].len(), 2);
+    assert_eq!(records[1]["Name"], "Bob".into());
+    assert_eq!(records[1]["Age"], "nested".into());
+
+    // the nested table has its own records
+    let nested_sel = doc.select("#people table td");
+    let nested_cell = nested_sel.nodes().first().unwrap();
+    let nested = nested_cell.closest_table().unwrap();
+    assert_ne!(nested.id, table.id);
+    assert!(nested.table_to_records().is_empty());
+
+    let doc = Document::from("<div><p>No table</p></div>");
+    let p_sel = doc.select("p");
+    let p = p_sel.nodes().first().unwrap();
+    assert!(p.closest_table().is_none());
+    assert!(p.table_to_records().is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_descendants_matching() {
+    let doc: Document = ANCESTORS_CONTENTS.into();
+    let sel = doc.select_single("#great-ancestor");
+    let node = sel.nodes().first().unwrap();
+
+    for selector in [
+        "div",
+        ".child",
+        "#parent > div",
+        ":scope > div",
+        "div + div",
+    ] {
+        let node_ids: Vec<_> = node
+            .descendants_matching(selector)
+            .iter()
+            .map(|n| n.id)
+            .collect();
+        let sel_ids: Vec<_> = sel.select(selector).nodes().iter().map(|n| n.id).collect();
+        assert_eq!(node_ids, sel_ids, "selector: {}", selector);
+    }
+
+    // the node itself is not included
+    assert!(node.descendants_matching("#great-ancestor").is_empty());
+    assert_eq!(node.descendants_matching(":scope > div").len(), 2);
+
+    assert_eq!(node.try_descendants_matching(".child").unwrap().len(), 2);
+    assert!(node.try_descendants_matching("span").unwrap().is_empty());
+    // invalid selector
+    assert!(node.try_descendants_matching("div:(").is_none());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[should_panic]
+fn test_descendants_matching_invalid() {
+    let doc: Document = ANCESTORS_CONTENTS.into();
+    let sel = doc.select_single("#great-ancestor");
+    let node = sel.nodes().first().unwrap();
+    node.descendants_matching("div:(");
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_child_count() {
+    let contents = r#"<div id="main">text<!-- comment --><p>1</p> <span>2</span><b></b>tail</div><div id="empty"></div>"#;
+    let doc = Document::from(contents);
+
+    let sel = doc.select("#main");
+    let main = sel.nodes().first().unwrap();
+    // text, comment, p, whitespace, span, b, text
+    assert_eq!(main.child_count(), 7);
+    assert_eq!(main.child_count(), main.children().len());
+    assert_eq!(main.element_child_count(), 3);
+    assert_eq!(main.element_child_count(), main.element_children().len());
+
+    let sel = doc.select("#empty");
+    let empty = sel.nodes().first().unwrap();
+    assert_eq!(empty.child_count(), 0);
+    assert_eq!(empty.element_child_count(), 0);
+
+    // a text node has no children
+    let text = main.first_child().unwrap();
+    assert_eq!(text.child_count(), 0);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_has_child_matching() {
+    let contents = r#"<div id="main">
+        text
+        <ul class="menu"><li class="active">1</li></ul>
+        <p>Paragraph</p>
+    </div>"#;
+    let doc = Document::from(contents);
+    let sel = doc.select("#main");
+    let main = sel.nodes().first().unwrap();
+
+    // direct children
+    assert!(main.has_child_matching("ul.menu"));
+    assert!(main.has_child_matching("p"));
+    assert!(main.has_child_matching("ul + p"));
+    // a grandchild is not a child
+    assert!(!main.has_child_matching("li.active"));
+    assert!(doc.select("#main:has(li.active)").exists());
+    assert!(!doc.select("#main:has(> li.active)").exists());
+    // the node itself is not tested
+    assert!(!main.has_child_matching("#main"));
+
+    assert_eq!(main.try_has_child_matching("p"), Some(true));
+    assert_eq!(main.try_has_child_matching("li"), Some(false));
+    // invalid selector
+    assert_eq!(main.try_has_child_matching("p["), None);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[should_panic]
+fn test_node_has_child_matching_invalid() {
+    let doc = Document::from(r#"<div id="main"><p>Paragraph</p></div>"#);
+    let sel = doc.select("#main");
+    let main = sel.nodes().first().unwrap();
+    main.has_child_matching("p[");
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_adjacent_text() {
+    let contents = r#"<ul>
+        <li id="price"><strong>Price:</strong> 25 EUR</li>
+        <li id="color">Color: <strong>red</strong></li>
+        <li id="size"><strong>Size:</strong><span>XL</span></li>
+    </ul>"#;
+    let doc = Document::from(contents);
+
+    let label = doc.select("#price strong").nodes()[0].clone();
+    assert_eq!(label.next_text().unwrap().trim(), "25 EUR");
+    assert_eq!(label.previous_text(), None);
+
+    let value = doc.select("#color strong").nodes()[0].clone();
+    assert_eq!(value.previous_text(), Some("Color: ".into()));
+    assert_eq!(value.next_text(), None);
+
+    // the next sibling is an element
+    let label = doc.select("#size strong").nodes()[0].clone();
+    assert_eq!(label.next_text(), None);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_closest_block() {
+    let contents = r#"<div id="main">
+        <p id="para">Some <b><i id="in-p">text</i></b></p>
+        <span><a id="in-div" href="/">link</a></span>
+    </div>"#;
+    let doc = Document::from(contents);
+
+    let sel = doc.select("#in-p");
+    let node = sel.nodes().first().unwrap();
+    let block = node.closest_block().unwrap();
+    assert_eq!(block.attr("id").unwrap().as_ref(), "para");
+    // the text node inside the inline element
+    let text = node.first_child().unwrap();
+    assert!(text.is_text());
+    assert_eq!(text.closest_block().unwrap().id, block.id);
+
+    let sel = doc.select("#in-div");
+    let node = sel.nodes().first().unwrap();
+    let block = node.closest_block().unwrap();
+    assert_eq!(block.attr("id").unwrap().as_ref(), "main");
+
+    // a block element is its own closest block
+    assert_eq!(block.closest_block().unwrap().id, block.id);
+
+    // the document itself is not an element
+    assert!(doc.root().closest_block().is_none());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_index() {
+    let doc = Document::from(
+        r#"<ul id="list"><li id="first">One</li> text <!-- c --><li id="second">Two</li></ul>"#,
+    );
+
+    let first = doc.select_single("#first").nodes()[0].clone();
+    let second = doc.select_single("#second").nodes()[0].clone();
+    assert_eq!(first.index(), 0);
+    assert_eq!(second.index(), 1);
+    assert_eq!(second.index_of_kind(false), 3);
+    assert_eq!(first.index_of_kind(false), 0);
+    assert_eq!(doc.root().index(), 0);
+
+    assert_eq!(doc.select("#second").index(), Some(1));
+    assert_eq!(doc.select("li").index(), Some(0));
+    assert_eq!(doc.select("#missing").index(), None);
+    assert_eq!(Selection::from(doc.root()).index(), None);
+}
@@ -91,3 +91,60 @@ fn doc_is_send() {
     fn is_send<T: Send>() {}
     is_send::<Document>();
 }
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn parse_fragment_with_table_context() {
+    let contents = "<tr><td>x</td></tr>";
+
+    // in the default `body` context table rows and cells are dropped
+    let fragment = Document::fragment(contents);
+    assert!(!fragment.select("tr").exists());
+    assert!(!fragment.select("td").exists());
+
+    let fragment = Document::fragment_with_context(contents, "table");
+    assert!(fragment.root().is_fragment());
+    // the parser inserts an implied `tbody`, like browsers do
+    assert_eq!(fragment.select("tbody > tr > td").length(), 1);
+    assert_eq!(fragment.select("td").text(), "x".into());
+
+    let html = fragment.select("html").inner_html();
+    assert_eq!(html, "<tbody><tr><td>x</td></tr></tbody>".into());
+
+    // serialized html survives re-parsing in the same context
+    let reparsed = Document::fragment_with_context(html.clone(), "table");
+    assert_eq!(reparsed.select("html").inner_html(), html);
+    assert_eq!(reparsed.select("tbody > tr > td").length(), 1);
+
+    // in a `tbody` context the rows are kept as is
+    let fragment = Document::fragment_with_context(contents, "tbody");
+    assert_eq!(fragment.select("html").inner_html(), contents.into());
+
+    // the context name is case-insensitive
+    let fragment = Document::fragment_with_context(contents, "TABLE");
+    assert_eq!(
+        fragment.select("html").inner_html(),
+        "<tbody><tr><td>x</td></tr></tbody>".into()
+    );
+    let fragment = Document::fragment_with_context(contents, "TBody");
+    assert_eq!(fragment.select("html").inner_html(), contents.into());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn parse_doc_lossy_bytes() {
+    let mut contents = b"<html><head><title>T\xFFst</title></head><body><p id=\"first\">".to_vec();
+    // a truncated multi-byte sequence
+    contents.extend_from_slice(&[0xE2, 0x82]);
+    contents.extend_from_slice(b" price</p><p id=\"second\">Caf\xC3\xA9</p></body></html>");
+    assert!(std::str::from_utf8(&contents).is_err());
+
+    let doc = Document::from_str_lossy(&contents);
+    assert_eq!(doc.select("title").text().as_ref(), "T\u{FFFD}st");
+    assert_eq!(doc.select("#first").text().as_ref(), "\u{FFFD} price");
+    // valid sequences are kept
+    assert_eq!(doc.select("#second").text().as_ref(), "Café");
+
+    let valid = Document::from_str_lossy(HEADING_CONTENTS.as_bytes());
+    assert_eq!(valid.html(), Document::from(HEADING_CONTENTS).html());
+}
@@ -286,7 +286,7 @@ fn test_only_of_type() {
 fn test_unsupported_pseudo_class() {
     let doc: Document = SIMPLE_LIST_CONTENT.into();
 
-    doc.select("*:required");
+    doc.select("*:invalid");
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), test)]
@@ -294,7 +294,7 @@ fn test_unsupported_pseudo_class() {
 fn test_try_unsupported_pseudo_class() {
     let doc: Document = SIMPLE_LIST_CONTENT.into();
 
-    let sel = doc.try_select("*:required");
+    let sel = doc.try_select("*:invalid");
 
     assert!(sel.is_none());
 }
@@ -307,3 +307,225 @@ fn test_unsupported_functional_pseudo_class() {
 
     doc.select(":lang(en-US)");
 }
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_scope_pseudo_class() {
+    let contents = r#"<ul id="outer">
+        <li>One</li>
+        <li>Two
+            <ul id="inner">
+                <li>Two A</li>
+                <li>Two B</li>
+            </ul>
+        </li>
+    </ul>"#;
+    let doc = Document::from(contents);
+    let outer = doc.select("#outer");
+
+    // `li` matches all descendants, `:scope > li` only direct children of the selection root
+    assert_eq!(outer.select("li").length(), 4);
+    assert_eq!(outer.select(":scope > li").length(), 2);
+
+    let inner = doc.select("#inner");
+    let texts: Vec<_> = inner
+        .select(":scope > li")
+        .iter()
+        .map(|n| n.text())
+        .collect();
+    assert_eq!(texts, vec!["Two A".into(), "Two B".into()]);
+
+    // each root of the selection is a scope for its own descendants
+    let both = doc.select("ul");
+    assert_eq!(both.select(":scope > li").length(), 4);
+    assert_eq!(both.select(":scope > li > ul > li").length(), 2);
+
+    assert_eq!(outer.select_single(":scope > li").text(), "One".into());
+    assert!(outer.select(":scope").is_empty());
+}
+
+const FORM_CONTENT: &str = r#"<!DOCTYPE html>
+    <html>
+        <head><title>Test</title></head>
+        <body>
+            <form>
+                <input type="checkbox" id="cb-1" name="a" checked>
+                <input type="checkbox" id="cb-2" name="b">
+                <input type="RADIO" id="r-1" name="c" checked>
+                <input type="text" id="t-1" name="d" checked required>
+                <input type="text" id="t-2" name="e" disabled>
+                <select id="s-1" name="f" required>
+                    <option id="o-1">One</option>
+                    <option id="o-2" selected>Two</option>
+                </select>
+                <fieldset id="fs-1" disabled>
+                    <input type="text" id="t-3" name="g">
+                    <textarea id="ta-1" name="h"></textarea>
+                </fieldset>
+                <button id="btn-1">Send</button>
+                <a id="a-1" href="/" disabled>Link</a>
+            </form>
+        </body>
+    </html>"#;
+
+fn ids(sel: &dom_query::Selection) -> Vec<String> {
+    sel.iter()
+        .map(|n| n.attr_or("id", "").to_string())
+        .collect()
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_pseudo_class_checked() {
+    let doc: Document = FORM_CONTENT.into();
+
+    // only checkboxes and radio buttons can be checked, a `checked` text input is ignored
+    let sel = doc.select("input:checked");
+    assert_eq!(ids(&sel), vec!["cb-1", "r-1"]);
+
+    let sel = doc.select("option:checked");
+    assert_eq!(ids(&sel), vec!["o-2"]);
+
+    let sel = doc.select("input[type=checkbox]:not(:checked)");
+    assert_eq!(ids(&sel), vec!["cb-2"]);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_pseudo_class_disabled_enabled() {
+    let doc: Document = FORM_CONTENT.into();
+
+    // descendants of a disabled fieldset are disabled too, non-form elements are never matched
+    let sel = doc.select(":disabled");
+    assert_eq!(ids(&sel), vec!["t-2", "fs-1", "t-3", "ta-1"]);
+
+    let sel = doc.select("input:enabled");
+    assert_eq!(ids(&sel), vec!["cb-1", "cb-2", "r-1", "t-1"]);
+
+    assert!(doc.select("#btn-1").is(":enabled"));
+    assert!(!doc.select("#a-1").is(":enabled"));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_pseudo_class_required_optional() {
+    let doc: Document = FORM_CONTENT.into();
+
+    let sel = doc.select(":required");
+    assert_eq!(ids(&sel), vec!["t-1", "s-1"]);
+
+    let sel = doc.select("input:optional");
+    assert_eq!(ids(&sel), vec!["cb-1", "cb-2", "r-1", "t-2", "t-3"]);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_pseudo_class_has_deep_nesting() {
+    const DEPTH: usize = 300;
+    let mut contents = String::from("<body>");
+    // a deep chain with an image at the bottom
+    contents.push_str(&r#"<div class="with-img">"#.repeat(DEPTH));
+    contents.push_str(r#"<img id="deep-img" class="marker" data-marker="1" src="a.png">"#);
+    contents.push_str(&"</div>".repeat(DEPTH));
+    // a deep chain without any image
+    contents.push_str(&r#"<div class="without-img">"#.repeat(DEPTH));
+    contents.push_str("<span>text</span>");
+    contents.push_str(&"</div>".repeat(DEPTH));
+    contents.push_str("</body>");
+    let doc = Document::from(contents);
+
+    let divs = doc.select("div");
+    assert_eq!(divs.length(), DEPTH * 2);
+    let expected: Vec<_> = divs
+        .nodes()
+        .iter()
+        .filter(|div| div.descendants().iter().any(|d| d.has_name("img")))
+        .map(|div| div.id)
+        .collect();
+    assert_eq!(expected.len(), DEPTH);
+
+    // several relative selectors on the same anchor also check the subtree's bloom filter,
+    // which must contain the id, the classes and the attribute names of the descendants
+    for sel in [
+        "div:has(img)",
+        "div:has(video, img)",
+        "div:has(p, #deep-img)",
+        "div:has(p, .marker)",
+        "div:has(p, [data-marker])",
+        "div:has(p):has(img), div:has(img)",
+    ] {
+        let matched: Vec<_> = doc.select(sel).nodes().iter().map(|n| n.id).collect();
+        assert_eq!(matched.len(), expected.len(), "{}", sel);
+        assert!(matched.iter().all(|id| expected.contains(id)), "{}", sel);
+        assert!(divs
+            .filter(sel)
+            .nodes()
+            .iter()
+            .map(|n| n.id)
+            .eq(expected.iter().copied()));
+    }
+
+    assert_eq!(doc.select("div:not(:has(img))").length(), DEPTH);
+    assert_eq!(doc.select("div:has(> img)").length(), 1);
+    assert_eq!(doc.select("div:has(video, audio)").length(), 0);
+    assert_eq!(doc.select("div.with-img:has(span)").length(), 0);
+    assert!(doc.select("body").is("body:has(video, img)"));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_pseudo_class_has_keeps_matches() {
+    let contents = r#"<body>
+        <section id="s-id"><div><div><p><span id="target">id</span></p></div></div></section>
+        <section id="s-class"><div><div><p><span class="hit marked">class</span></p></div></div></section>
+        <section id="s-attr"><div><div><p><span data-role="x">attr</span></p></div></div></section>
+        <section id="s-mixed"><div><div><p><span data-Mixed-Case="1">mixed</span></p></div></div></section>
+        <section id="s-svg"><div><div><svg viewBox="0 0 10 10"><linearGradient id="grad"></linearGradient><circle class="dot" r="1"></circle></svg></div></div></section>
+        <section id="s-math"><div><div><math><mi mathvariant="bold">x</mi></math></div></div></section>
+        <section id="s-none"><div><div><p><span>plain</span></p></div></div></section>
+    </body>"#;
+    let doc = Document::from(contents);
+    let sections = doc.select("section");
+
+    for (inner, expected) in [
+        ("#target", vec!["s-id"]),
+        ("span#target", vec!["s-id"]),
+        (".hit", vec!["s-class"]),
+        (".hit.marked", vec!["s-class"]),
+        ("[data-role]", vec!["s-attr"]),
+        ("[data-role=x]", vec!["s-attr"]),
+        ("[data-mixed-case]", vec!["s-mixed"]),
+        ("[data-Mixed-Case]", vec!["s-mixed"]),
+        ("svg", vec!["s-svg"]),
+        ("circle.dot", vec!["s-svg"]),
+        ("#grad", vec!["s-svg"]),
+        ("linearGradient", vec!["s-svg"]),
+        ("[viewBox]", vec!["s-svg"]),
+        ("svg [r]", vec!["s-svg"]),
+        ("math mi", vec!["s-math"]),
+        ("[mathvariant=bold]", vec!["s-math"]),
+        ("#missing", vec![]),
+        (".dot, .hit", vec!["s-class", "s-svg"]),
+    ] {
+        // the result must be the same as searching the subtree of each section directly
+        let brute: Vec<String> = sections
+            .iter()
+            .filter(|s| s.select(inner).exists())
+            .map(|s| s.attr("id").unwrap().to_string())
+            .collect();
+        assert_eq!(brute, expected, "{}", inner);
+
+        // a leading `video` makes the second relative selector check the anchor's subtree bloom filter
+        for sel in [
+            format!("section:has({})", inner),
+            format!("section:has(video, {})", inner),
+        ] {
+            let matched: Vec<String> = doc
+                .select(&sel)
+                .iter()
+                .map(|s| s.attr("id").unwrap().to_string())
+                .collect();
+            assert_eq!(matched, expected, "{}", sel);
+        }
+    }
+}
@@ -330,3 +330,390 @@ fn test_prepend_another_tree_selection() {
         2
     );
 }
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_rename_descendants() {
+    let contents = r#"<!DOCTYPE html>
+    <html>
+        <head><title>Test</title></head>
+        <body>
+            <div id="main">
+                <p><b>Bold</b> and <i>italic</i></p>
+                <p><b class="x">Another <i>nested</i> bold</b></p>
+            </div>
+            <div id="footer"><b>Footer</b></div>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+
+    let sel = doc.select("html");
+    sel.rename_descendants("b", "strong");
+    sel.rename_descendants("i", "em");
+
+    assert!(!doc.select("b, i").exists());
+    assert_eq!(doc.select("strong").length(), 3);
+    assert_eq!(doc.select("em").length(), 2);
+    // attributes and content are preserved
+    assert_eq!(
+        doc.select("strong.x").inner_html(),
+        "Another <em>nested</em> bold".into()
+    );
+
+    // renaming is limited to descendants of the selection
+    let doc = Document::from(contents);
+    doc.select("#main").rename_descendants("b", "strong");
+    assert_eq!(doc.select("#main strong").length(), 2);
+    assert!(doc.select("#footer > b").exists());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_map_attr() {
+    let contents = r#"<div>
+        <input name="first" value="one">
+        <input name="second" value="two">
+        <input name="third">
+        <span value="four"></span>
+    </div>"#;
+    let doc = Document::from(contents);
+    let sel = doc.select("input");
+    sel.map_attr("value", |val| Some(val.to_uppercase()));
+
+    assert_eq!(
+        doc.select("input[name=first]").attr("value").unwrap(),
+        "ONE".into()
+    );
+    assert_eq!(
+        doc.select("input[name=second]").attr("value").unwrap(),
+        "TWO".into()
+    );
+    // elements without the attribute are left untouched
+    assert!(!doc.select("input[name=third]").has_attr("value"));
+    // elements outside the selection are not affected
+    assert_eq!(doc.select("span").attr("value").unwrap(), "four".into());
+
+    // returning `None` removes the attribute
+    sel.map_attr("value", |val| (val != "ONE").then(|| val.to_lowercase()));
+    assert!(!doc.select("input[name=first]").has_attr("value"));
+    assert_eq!(
+        doc.select("input[name=second]").attr("value").unwrap(),
+        "two".into()
+    );
+    assert!(!doc.select("input[name=third]").has_attr("value"));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_wrap_each_html() {
+    let contents = r#"<div id="gallery">
+        <img src="1.png">
+        <img src="2.png">
+        <p><img src="3.png"></p>
+    </div>"#;
+    let doc = Document::from(contents);
+    let sel = doc.select("img");
+    sel.wrap_each_html(r#"<a class="zoom"><span></span></a>"#);
+
+    // each image has its own wrapper, the image goes into the innermost element
+    assert_eq!(doc.select("a.zoom").length(), 3);
+    assert_eq!(doc.select("a.zoom > span > img").length(), 3);
+    assert_eq!(doc.select("#gallery > a.zoom").length(), 2);
+    assert_eq!(doc.select("#gallery > p > a.zoom").length(), 1);
+    for a in doc.select("a.zoom").iter() {
+        assert_eq!(a.select("img").length(), 1);
+    }
+    // the order of wrapped nodes is preserved
+    let srcs: Vec<_> = doc
+        .select("a.zoom img")
+        .iter()
+        .map(|img| img.attr("src").unwrap())
+        .collect();
+    assert_eq!(srcs, vec!["1.png".into(), "2.png".into(), "3.png".into()]);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_wrap_each_html_without_elements() {
+    let doc = Document::from(r#"<div id="main"><span>1</span></div>"#);
+    doc.select("span").wrap_each_html("just text");
+    assert_eq!(doc.select("#main").inner_html(), "<span>1</span>".into());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_html_tidy() {
+    let contents = r#"<!DOCTYPE html>
+    <html>
+        <head><title>Test</title></head>
+        <body>
+            <article id="post">
+                <h1>Title</h1>
+                <ul>
+                    <li>One</li>
+                    <li><b>Two</b> <i>and a half</i></li>
+                </ul>
+                <pre>
+  keep
+    me  </pre>
+                <p>  Text <span> </span></p>
+            </article>
+        </body>
+    </html>"#;
+    let doc = Document::from(contents);
+    let sel = doc.select("#post");
+    sel.append_html("<p>Foo</p>");
+    let p = doc.select("#post > p:last-child");
+    p.append_html(" bar");
+    p.append_html(" baz");
+
+    sel.html_tidy();
+
+    let expected = "<h1>Title</h1>\
+    <ul><li>One</li><li><b>Two</b> <i>and a half</i></li></ul>\
+    <pre>  keep\n    me  </pre>\
+    <p>  Text <span> </span></p>\
+    <p>Foo bar baz</p>";
+    assert_eq!(sel.inner_html(), expected.into());
+    // adjacent text nodes were merged
+    let last_p = doc.select("#post > p:last-child");
+    assert_eq!(last_p.nodes()[0].children().len(), 1);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_text_map() {
+    let contents = r#"<div id="main" class="content">
+        <!--a comment-->
+        <p title="Title">Hello, <a href="/world">world</a>!</p>
+        <p>Second paragraph</p>
+    </div>
+    <p id="outside">outside</p>"#;
+    let doc = Document::from(contents);
+
+    // nested nodes in the selection are transformed only once
+    let sel = doc.select("#main, #main p");
+    sel.text_map(|text| text.to_uppercase());
+
+    let main = doc.select("#main");
+    assert_eq!(main.select("p").first().text(), "HELLO, WORLD!".into());
+    assert_eq!(main.select("p").last().text(), "SECOND PARAGRAPH".into());
+    // attributes, element names and comments are not touched
+    assert!(main.is(".content"));
+    assert_eq!(main.select("p").attr("title").unwrap(), "Title".into());
+    assert_eq!(main.select("a").attr("href").unwrap(), "/world".into());
+    assert!(main.html().contains("<!--a comment-->"));
+    // nodes outside the selection are not touched
+    assert_eq!(doc.select("#outside").text(), "outside".into());
+
+    let sel = doc.select("#main a");
+    sel.text_map(|text| text.replace("WORLD", "there"));
+    assert_eq!(main.select("p").first().text(), "HELLO, there!".into());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_unwrap_all_matching() {
+    let contents = r#"<div id="main">
+        <p>The <span class="highlight">quick <span class="highlight">brown</span></span> fox
+        jumps over the <span class="keep">lazy</span> dog.</p>
+        <p><span class="highlight" data-note="x"><em>Emphasized</em></span></p>
+        <span class="highlight"></span>
+    </div>
+    <span class="highlight" id="outside">outside</span>"#;
+    let doc = Document::from(contents);
+    let main = doc.select("#main");
+    let text_before = main.text();
+
+    main.unwrap_all_matching("span.highlight");
+
+    // matched elements are removed, but their contents are kept in place
+    assert!(!main.select(".highlight").exists());
+    assert_eq!(main.text(), text_before);
+    assert_eq!(
+        main.select("p").first().inner_html(),
+        r#"The quick brown fox
+        jumps over the <span class="keep">lazy</span> dog."#
+            .into()
+    );
+    assert_eq!(
+        main.select("p").last().inner_html(),
+        "<em>Emphasized</em>".into()
+    );
+    // elements outside the selection are not touched
+    assert!(doc.select("#outside.highlight").exists());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_merge_adjacent() {
+    let doc = Document::from(r#"<p id="simple"><b>a</b><b>b</b></p>"#);
+    doc.select("b").merge_adjacent();
+    assert_eq!(doc.select("#simple").inner_html().as_ref(), "<b>ab</b>");
+
+    let contents = r#"<div id="main"><b class="first">a<i>b</i></b><b>c</b><i>d</i><b>e</b><b>f</b><b>g</b> <b>h</b><b class="skip">i</b></div>"#;
+    let doc = Document::from(contents);
+    let main = doc.select("#main");
+    // the last `<b>` is not matched, so it stays separate
+    main.select("b:not(.skip), i").merge_adjacent();
+    assert_eq!(
+        main.inner_html().as_ref(),
+        r#"<b class="first">a<i>b</i>c</b><i>d</i><b>efg</b> <b>h</b><b class="skip">i</b>"#
+    );
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_wrap_with_node() {
+    let contents = r#"<div id="gallery">
+        <img src="1.png">
+        <img src="2.png">
+        <p><img src="3.png"></p>
+    </div>"#;
+    let doc = Document::from(contents);
+
+    // a prepared wrapper from another document
+    let template = Document::fragment(r#"<a class="zoom"><span></span></a>"#);
+    let wrapper_sel = template.select("a.zoom");
+    let wrapper = wrapper_sel.nodes().first().unwrap();
+
+    doc.select("img").wrap_with_node(wrapper);
+
+    assert_eq!(doc.select("a.zoom > span > img").length(), 3);
+    assert_eq!(doc.select("#gallery > a.zoom").length(), 2);
+    assert_eq!(doc.select("#gallery > p > a.zoom").length(), 1);
+    for a in doc.select("a.zoom").iter() {
+        assert_eq!(a.select("img").length(), 1);
+    }
+    // the wrapper itself is not changed
+    assert_eq!(
+        template.select("html").inner_html().as_ref(),
+        r#"<a class="zoom"><span></span></a>"#
+    );
+
+    // a wrapper from the same document
+    let doc = Document::from(r#"<p><b>1</b><b>2</b></p>"#);
+    let wrapper = doc.tree.new_element("i");
+    doc.select("b").wrap_with_node(&wrapper);
+    assert_eq!(
+        doc.select("p").inner_html().as_ref(),
+        "<i><b>1</b></i><i><b>2</b></i>"
+    );
+    assert!(wrapper.parent().is_none());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_node_wrap_node() {
+    let doc = Document::from(r#"<div id="main"><span>1</span><section><em></em></section></div>"#);
+    let span_sel = doc.select("span");
+    let span = span_sel.nodes().first().unwrap();
+    let section_sel = doc.select("section");
+    let section = section_sel.nodes().first().unwrap();
+
+    // an existing element is moved, the node goes into its innermost first element
+    span.wrap_node(section);
+    assert_eq!(
+        doc.select("#main").inner_html().as_ref(),
+        "<section><em><span>1</span></em></section>"
+    );
+
+    // a node can't be wrapped with itself or its descendant
+    let main_sel = doc.select("#main");
+    let main = main_sel.nodes().first().unwrap();
+    main.wrap_node(section);
+    main.wrap_node(main);
+    assert_eq!(
+        doc.select("body").inner_html().as_ref(),
+        r#"<div id="main"><section><em><span>1</span></em></section></div>"#
+    );
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_keep_only_text() {
+    let contents = r#"<table>
+        <tr><td class="cell"><b>Bold</b> and <i>italic <u>nested</u></i> &amp; more</td><td class="cell"></td></tr>
+        <tr><td class="cell">Plain</td><td><b>untouched</b></td></tr>
+    </table>"#;
+    let doc = Document::from(contents);
+    let cells = doc.select("td.cell");
+    cells.keep_only_text();
+
+    let first = cells.nodes().first().unwrap();
+    assert_eq!(first.children().len(), 1);
+    assert!(first.first_child().unwrap().is_text());
+    assert_eq!(first.text().as_ref(), "Bold and italic nested & more");
+    // the text is escaped on serialization
+    assert_eq!(
+        first.html().as_ref(),
+        r#"<td class="cell">Bold and italic nested &amp; more</td>"#
+    );
+    assert_eq!(cells.nodes()[1].text().as_ref(), "");
+    assert_eq!(
+        cells.nodes()[2].html().as_ref(),
+        r#"<td class="cell">Plain</td>"#
+    );
+
+    assert!(!doc.select("td.cell b, td.cell i").exists());
+    // other cells are not changed
+    assert!(doc.select("td:not(.cell) > b").exists());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_remove_empty() {
+    let contents = r#"<div id="main"><p>Text</p><p>  </p><p><!-- comment --></p><p><img src="a.png"></p><p></p><span></span></div>"#;
+    let doc = Document::from(contents);
+
+    let removed = doc.select("#main p").remove_empty();
+    assert_eq!(removed, 3);
+    assert_eq!(
+        doc.select("#main").inner_html().as_ref(),
+        r#"<p>Text</p><p><img src="a.png"></p><span></span>"#
+    );
+
+    assert_eq!(doc.select("#main p").remove_empty(), 0);
+    assert_eq!(doc.select("#missing").remove_empty(), 0);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_set_inner_html_each() {
+    let contents = r#"<div id="grid">
+        <div class="card"><p>old</p></div>
+        <div class="card"></div>
+        <div class="card">old text</div>
+    </div>"#;
+    let doc = Document::from(contents);
+    let cards = doc.select(".card");
+
+    assert!(cards.set_inner_html_each(&[
+        "<h3>First</h3>",
+        r#"<h3>Second</h3><img src="b.png">"#,
+        "Third",
+    ]));
+    let htmls: Vec<_> = cards.iter().map(|card| card.inner_html()).collect();
+    assert_eq!(
+        htmls,
+        vec![
+            "<h3>First</h3>".into(),
+            r#"<h3>Second</h3><img src="b.png">"#.into(),
+            "Third".into(),
+        ]
+    );
+    assert!(!doc.select("#grid p").exists());
+
+    let expected = htmls;
+
+    // on length mismatch nothing is changed
+    assert!(!cards.set_inner_html_each(&["<b>1</b>"]));
+    assert!(!cards.set_inner_html_each(&["<i>a</i>", "<i>b</i>", "<i>c</i>", "<i>d</i>"]));
+    assert!(!cards.set_inner_html_each(&[]));
+    let htmls: Vec<_> = cards.iter().map(|card| card.inner_html()).collect();
+    assert_eq!(htmls, expected);
+    assert!(!doc.select("#grid b, #grid i").exists());
+
+    // an empty selection with an empty slice is a match
+    assert!(doc.select(".missing").set_inner_html_each(&[]));
+}
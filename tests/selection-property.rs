@@ -157,6 +157,34 @@ fn has_class_not_first() {
     assert!(sel.has_class("alert-error"));
 }
 
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_has_classes() {
+    let doc: Document =
+        r#"<a id="a" class="btn primary">A</a><a id="b" class="btn">B</a><p>No class</p>"#.into();
+
+    let a = doc.select("#a");
+    assert!(a.has_classes(&["btn", "primary"]));
+    assert!(a.has_classes(&["primary btn"]));
+    assert!(!a.has_classes(&["btn", "prim"]));
+    assert!(a.has_classes(&[]));
+    assert!(a.has_classes(&["  "]));
+
+    // true only if a single element has all the classes
+    let links = doc.select("a");
+    assert!(links.has_classes(&["btn", "primary"]));
+    assert!(!doc.select("#b").has_classes(&["btn", "primary"]));
+
+    let p = doc.select("p");
+    assert!(p.has_classes(&[]));
+    assert!(!p.has_classes(&["btn"]));
+    assert!(!doc.select("table").has_classes(&[]));
+
+    let node = a.nodes()[0].clone();
+    assert!(node.has_classes(&["btn", "primary"]));
+    assert!(!node.first_child().unwrap().has_classes(&[]));
+}
+
 #[cfg_attr(not(target_arch = "wasm32"), test)]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
 fn test_remove_class() {
@@ -180,6 +208,139 @@ fn test_remove_class_similar() {
     assert!(sel.has_class("odder"));
 }
 
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_replace_class() {
+    let doc: Document = r#"<div id="a" class="x old y">A</div>
+    <div id="b" class="x y">B</div>
+    <div id="c" class="new old z">C</div>
+    <div id="d">D</div>"#
+        .into();
+    let class_of = |id: &str| doc.select(id).attr("class").map(|c| c.to_string());
+
+    doc.select("div").replace_class("old", "new");
+    assert_eq!(class_of("#a").as_deref(), Some("x new y"));
+    assert_eq!(class_of("#b").as_deref(), Some("x y"));
+    // an already present new class is not duplicated
+    assert_eq!(class_of("#c").as_deref(), Some("new z"));
+    assert_eq!(class_of("#d"), None);
+
+    let node = doc.select("#a").nodes()[0].clone();
+    node.replace_class("missing", "other");
+    assert_eq!(class_of("#a").as_deref(), Some("x new y"));
+    node.replace_class("x", "");
+    assert_eq!(class_of("#a").as_deref(), Some("x new y"));
+    node.replace_class("x", "w");
+    assert_eq!(class_of("#a").as_deref(), Some("w new y"));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_toggle_class() {
+    let doc: Document =
+        r#"<div id="a" class="item active">A</div><div id="b" class="item">B</div>"#.into();
+    let sel = doc.select("div");
+
+    // each element is toggled according to its own state
+    sel.toggle_class("active");
+    assert_eq!(doc.select("#a").attr("class").unwrap(), "item".into());
+    assert_eq!(
+        doc.select("#b").attr("class").unwrap(),
+        "item active".into()
+    );
+
+    // multiple classes are toggled independently
+    sel.toggle_class("item  hidden");
+    assert_eq!(doc.select("#a").attr("class").unwrap(), "hidden".into());
+    assert_eq!(
+        doc.select("#b").attr("class").unwrap(),
+        "active hidden".into()
+    );
+
+    sel.toggle_class("  ");
+    assert_eq!(
+        doc.select("#b").attr("class").unwrap(),
+        "active hidden".into()
+    );
+
+    let node = doc.select("#a").nodes()[0].clone();
+    node.toggle_class("hidden");
+    assert!(!node.has_class("hidden"));
+    node.toggle_class("hidden");
+    assert!(node.has_class("hidden"));
+
+    let doc: Document = r#"<p>No class</p>"#.into();
+    let p = doc.select("p");
+    p.toggle_class("new");
+    assert_eq!(p.attr("class").unwrap(), "new".into());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_attr_as() {
+    let doc: Document = r#"<button id="btn" disabled tabindex="-1" data-count=" 42 " data-ratio="0.5" data-flag="false">OK</button>
+    <input id="inp" disabled="disabled" hidden="true" checked="1" data-size="big">
+    <select id="sel" disabled="false"></select>"#
+        .into();
+
+    let btn = doc.select("#btn");
+    assert!(btn.attr_as_bool("disabled"));
+    // presence alone means `true`, regardless of the value
+    assert!(btn.attr_as_bool("data-flag"));
+    assert_eq!(btn.attr_as_bool("disabled"), btn.is(":disabled"));
+    assert!(!btn.attr_as_bool("hidden"));
+    assert_eq!(btn.attr_as_int("tabindex"), Some(-1));
+    assert_eq!(btn.attr_as_int("data-count"), Some(42));
+    assert_eq!(btn.attr_as_int("data-ratio"), None);
+    assert_eq!(btn.attr_as::<f32>("data-ratio"), Some(0.5));
+    assert_eq!(btn.attr_as::<u8>("tabindex"), None);
+    assert_eq!(btn.attr_as::<bool>("data-flag"), Some(false));
+    assert_eq!(btn.attr_as::<i32>("missing"), None);
+
+    let node = doc.select("#inp").nodes()[0].clone();
+    assert!(node.attr_as_bool("disabled"));
+    assert!(node.attr_as_bool("hidden"));
+    assert!(node.attr_as_bool("checked"));
+    assert!(node.attr_as_bool("data-size"));
+    assert_eq!(node.attr_as_int("data-size"), None);
+
+    // `disabled="false"` still disables the element
+    let sel = doc.select("#sel");
+    assert!(sel.attr_as_bool("disabled"));
+    assert!(sel.is(":disabled"));
+
+    let empty = doc.select("table");
+    assert!(!empty.attr_as_bool("disabled"));
+    assert_eq!(empty.attr_as_int("tabindex"), None);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_set_attrs() {
+    let doc: Document = r#"<div id="a" class="x">A</div><div id="b">B</div>"#.into();
+    let sel = doc.select("div");
+
+    sel.set_attrs(&[("class", "y"), ("data-k", "1"), ("data-k", "2")]);
+    assert_eq!(
+        doc.select("#a").html(),
+        r#"<div id="a" class="y" data-k="2">A</div>"#.into()
+    );
+    assert_eq!(
+        doc.select("#b").html(),
+        r#"<div id="b" class="y" data-k="2">B</div>"#.into()
+    );
+
+    let node = doc.select("#b").nodes()[0].clone();
+    node.set_attrs(&[("id", "c"), ("title", "T")]);
+    assert_eq!(
+        node.html(),
+        r#"<div id="c" class="y" data-k="2" title="T">B</div>"#.into()
+    );
+
+    sel.set_attrs(&[]);
+    assert_eq!(doc.select("#a").attr("class").unwrap(), "y".into());
+}
+
 #[cfg_attr(not(target_arch = "wasm32"), test)]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
 fn test_remove_attrs() {
@@ -383,3 +544,95 @@ fn test_selection_try_inner_html() {
     let sel = doc.select("#parent > #third-child");
     assert_eq!(sel.try_inner_html(), None);
 }
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_extract_links() {
+    let contents = r##"<!DOCTYPE html>
+    <html>
+        <head><title>Test</title></head>
+        <body>
+            <nav><a href="/">Home</a></nav>
+            <article>
+                <p>Read the <a href="https://docs.rs/dom_query">
+                    documentation
+                </a> first.</p>
+                <p>Jump to <a name="footnotes">footnotes</a> or
+                <a href="#comments">comments</a>.</p>
+                <a href=""><img src="logo.png"></a>
+            </article>
+        </body>
+    </html>"##;
+    let doc = Document::from(contents);
+
+    let links = doc.select("article").extract_links();
+    assert_eq!(links.len(), 3);
+    assert_eq!(
+        links[0],
+        ("documentation".into(), "https://docs.rs/dom_query".into())
+    );
+    assert_eq!(links[1], ("comments".into(), "#comments".into()));
+    assert_eq!(links[2], ("".into(), "".into()));
+
+    // anchors in the selection itself are included, nodes are not duplicated
+    let links = doc.select("body, nav, nav a").extract_links();
+    assert_eq!(links.len(), 4);
+    assert_eq!(links[0], ("Home".into(), "/".into()));
+
+    assert!(doc.select("title").extract_links().is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_inner_texts() {
+    let contents = r#"<ul class="addresses">
+        <li>
+            <b>John   Doe</b><br>
+            221B Baker Street<br>London
+        </li>
+        <li>Jane Roe<br><br>Unknown</li>
+        <li>   </li>
+    </ul>"#;
+    let doc = Document::from(contents);
+
+    let texts = doc.select(".addresses li").inner_texts();
+    assert_eq!(
+        texts,
+        vec![
+            "John Doe\n221B Baker Street\nLondon".into(),
+            "Jane Roe\n\nUnknown".into(),
+            "".into(),
+        ]
+    );
+
+    // `text` keeps the original whitespace and ignores `<br>`
+    let text = doc.select(".addresses li").first().text();
+    assert!(text.contains("John   Doe"));
+
+    assert!(doc.select("p").inner_texts().is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_text_joined_by_node() {
+    let contents = r#"<table>
+        <tr><td></td></tr>
+        <tr><td> Row 1 </td><td>A</td></tr>
+        <tr><td>   </td></tr>
+        <tr><td></td></tr>
+        <tr><td>Row 2</td></tr>
+        <tr><td>
+        </td></tr>
+    </table>"#;
+    let doc = Document::from(contents);
+    let rows = doc.select("tr");
+    assert_eq!(rows.text_joined_by_node("\n").as_ref(), "Row 1 A\nRow 2");
+    assert_eq!(rows.text_joined_by_node(" | ").as_ref(), "Row 1 A | Row 2");
+
+    // no non-empty nodes
+    assert_eq!(
+        doc.select("td:empty").text_joined_by_node("\n").as_ref(),
+        ""
+    );
+    assert_eq!(doc.select("p").text_joined_by_node("\n").as_ref(), "");
+}
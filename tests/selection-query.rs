@@ -4,7 +4,7 @@ use std::collections::HashSet;
 
 use data::{doc, ANCESTORS_CONTENTS, HEADING_CONTENTS};
 
-use dom_query::{Document, Selection};
+use dom_query::{Document, Matcher, Selection};
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen_test::*;
 
@@ -177,3 +177,287 @@ fn test_selection_unique() {
     let unique_ids = sel_ids.iter().cloned().collect::<HashSet<_>>();
     assert_eq!(sel_ids.len(), unique_ids.len());
 }
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_filter_attr_contains() {
+    let contents = r#"<div>
+        <a id="a" title='Say "hi" to them'>A</a>
+        <a id="b" title="it's ]fine[">B</a>
+        <a id="c" title="nothing special">C</a>
+        <a id="d">D</a>
+    </div>"#;
+    let doc = Document::from(contents);
+    let links = doc.select("a");
+
+    // substrings with quotes and brackets would break a selector string
+    let needle = String::from(r#""hi""#);
+    let sel = links.filter_attr_contains("title", &needle);
+    assert_eq!(sel.length(), 1);
+    assert_eq!(sel.attr("id").unwrap(), "a".into());
+
+    let sel = links.filter_attr_contains("title", "'s ]fine[");
+    assert_eq!(sel.length(), 1);
+    assert_eq!(sel.attr("id").unwrap(), "b".into());
+
+    // elements without the attribute are never kept
+    assert_eq!(links.filter_attr_contains("title", "").length(), 3);
+    assert!(links.filter_attr_contains("title", "missing").is_empty());
+    assert!(links.filter_attr_contains("href", "").is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_with_and_without_attr() {
+    let contents = r#"<form>
+        <input name="login">
+        <input name="password" disabled>
+        <button type="submit" disabled="disabled">Send</button>
+        <button type="reset">Reset</button>
+    </form>"#;
+    let doc = Document::from(contents);
+    let controls = doc.select("input, button");
+    let attr = String::from("disabled");
+
+    let disabled = controls.with_attr(&attr);
+    assert_eq!(disabled.length(), 2);
+    let ids = |sel: &Selection| sel.nodes().iter().map(|n| n.id).collect::<Vec<_>>();
+    assert_eq!(ids(&disabled), ids(&controls.filter("[disabled]")));
+
+    let enabled = controls.without_attr(&attr);
+    assert_eq!(enabled.length(), 2);
+    assert_eq!(ids(&enabled), ids(&controls.filter(":not([disabled])")));
+    let names: Vec<_> = enabled
+        .iter()
+        .map(|n| n.attr("name").or_else(|| n.attr("type")).unwrap())
+        .collect();
+    assert_eq!(names, vec!["login".into(), "reset".into()]);
+
+    assert!(controls.with_attr("hidden").is_empty());
+    assert_eq!(controls.without_attr("hidden").length(), 4);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_same_nodes_as() {
+    let doc: Document = ANCESTORS_CONTENTS.into();
+
+    let children = doc.select(".child");
+    // identical
+    assert!(children.same_nodes_as(&doc.select("#parent > div")));
+    // reordered
+    let reordered = Selection::from(children.nodes().iter().rev().cloned().collect::<Vec<_>>());
+    assert_eq!(reordered.nodes()[0].id, children.nodes()[1].id);
+    assert!(children.same_nodes_as(&reordered));
+    assert!(reordered.same_nodes_as(&children));
+    // differing
+    let first = doc.select("#first-child");
+    assert!(first.is_selection(&children));
+    assert!(!first.same_nodes_as(&children));
+    assert!(!children.same_nodes_as(&first));
+    assert!(!children.same_nodes_as(&doc.select("#first-child, #parent")));
+    // empty selections
+    assert!(doc.select("table").same_nodes_as(&doc.select("form")));
+    assert!(!doc.select("table").same_nodes_as(&children));
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_sort_by_key() {
+    let contents = r#"<div class="cards">
+        <div class="card" data-price="25"><h3>Lamp</h3></div>
+        <div class="card" data-price="100"><h3>Chair</h3></div>
+        <div class="card" data-price="7"><h3>Cup</h3></div>
+        <div class="card"><h3>Unknown</h3></div>
+        <div class="card" data-price="25"><h3>Vase</h3></div>
+    </div>"#;
+    let doc = Document::from(contents);
+    let cards = doc.select(".card");
+
+    let sorted = cards.sort_by_key(|node| {
+        node.attr("data-price")
+            .and_then(|price| price.parse::<u32>().ok())
+            .unwrap_or(u32::MAX)
+    });
+    let titles: Vec<String> = sorted
+        .iter()
+        .map(|card| card.select("h3").text().to_string())
+        .collect();
+    // numeric order, not lexicographic; equal keys keep the document order
+    assert_eq!(titles, vec!["Cup", "Lamp", "Vase", "Chair", "Unknown"]);
+
+    // the original selection is untouched
+    assert_eq!(cards.nodes()[0].text().trim(), "Lamp");
+    assert_eq!(sorted.length(), cards.length());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_visible() {
+    let contents = r#"<div class="item">First</div>
+    <div class="item" style="display:none">Hidden</div>
+    <div class="item">Last</div>"#;
+    let doc = Document::from(contents);
+
+    let items = doc.select(".item");
+    assert_eq!(items.length(), 3);
+    let visible = items.visible();
+    assert_eq!(visible.length(), 2);
+    assert_eq!(visible.text().as_ref(), "FirstLast");
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_partition() {
+    let contents = r#"<nav>
+        <a href="/home" data-scope="internal">Home</a>
+        <a href="https://example.org" data-scope="external">Example</a>
+        <a href="/about" data-scope="internal">About</a>
+        <a href="https://github.com" data-scope="external">GitHub</a>
+    </nav>"#;
+    let doc = Document::from(contents);
+    let links = doc.select("a");
+
+    let (internal, external) = links.partition(r#"[data-scope="internal"]"#);
+    assert_eq!(internal.text().as_ref(), "HomeAbout");
+    assert_eq!(external.text().as_ref(), "ExampleGitHub");
+    // the same result as two separate filters
+    assert!(internal.same_nodes_as(&links.filter(r#"[data-scope="internal"]"#)));
+    assert!(external.same_nodes_as(&links.filter(r#":not([data-scope="internal"])"#)));
+
+    let (matched, rest) = links.partition("img");
+    assert!(matched.is_empty());
+    assert_eq!(rest.length(), 4);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_matcher_is_valid() {
+    let valid = [
+        "div",
+        "div > p.intro",
+        r#"a[href^="https"]"#,
+        "li:nth-child(2n+1)",
+        "p:has(> b), span:not(.hidden)",
+        ":scope > div",
+    ];
+    for sel in valid {
+        assert!(Matcher::is_valid(sel), "selector: {sel}");
+        assert_eq!(Matcher::parse_error(sel), None, "selector: {sel}");
+    }
+
+    let invalid = ["", "div >", "p[", "a:unknown-pseudo", "..class", "div,"];
+    for sel in invalid {
+        assert!(!Matcher::is_valid(sel), "selector: {sel}");
+        assert!(Matcher::parse_error(sel).is_some(), "selector: {sel}");
+    }
+
+    let err = Matcher::parse_error("div\n  p >").unwrap();
+    assert!(err.ends_with("(line 2, column 6)"), "{err}");
+
+    // errors are human-readable, not `Debug` dumps
+    assert_eq!(
+        Matcher::parse_error("div:foo").unwrap(),
+        "unsupported pseudo-class or pseudo-element: 'foo' (line 1, column 5)"
+    );
+    assert_eq!(
+        Matcher::parse_error("..class").unwrap(),
+        "expected a class name after '.', found: Delim('.') (line 1, column 2)"
+    );
+    assert!(Matcher::parse_error("p[")
+        .unwrap()
+        .starts_with("unexpected end of input"));
+    for sel in invalid {
+        let err = Matcher::parse_error(sel).unwrap();
+        assert!(err.starts_with(|c: char| c.is_ascii_lowercase()), "{err}");
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_closest_common_tag() {
+    let contents = r#"<div class="results">
+        <a class="item" href="/1">1</a>
+        <a class="item" href="/2">2</a>
+        <span class="item">3</span>
+    </div>"#;
+    let doc = Document::from(contents);
+
+    // homogeneous
+    assert_eq!(doc.select("a.item").closest_common_tag(), Some("a".into()));
+    assert_eq!(
+        doc.select(".results").closest_common_tag(),
+        Some("div".into())
+    );
+    // heterogeneous
+    assert_eq!(doc.select(".item").closest_common_tag(), None);
+    // empty
+    assert_eq!(doc.select("table").closest_common_tag(), None);
+    // not an element
+    let text_sel = Selection::from(doc.select("a").nodes()[0].first_child().unwrap());
+    assert_eq!(text_sel.closest_common_tag(), None);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_not() {
+    let contents = r#"<ul>
+        <li id="a">A</li>
+        <li id="b" class="disabled">B</li>
+        <li id="c">C</li>
+        <li id="d" class="disabled">D</li>
+    </ul>"#;
+    let doc = Document::from(contents);
+    let items = doc.select("li");
+    let ids = |sel: &Selection| -> Vec<String> {
+        sel.iter()
+            .map(|s| s.attr_or("id", "").to_string())
+            .collect()
+    };
+
+    let enabled = items.not(".disabled");
+    assert_eq!(ids(&enabled), vec!["a", "c"]);
+    assert!(enabled.same_nodes_as(&items.filter(":not(.disabled)")));
+
+    let matcher = Matcher::new("#a, #d").unwrap();
+    assert_eq!(ids(&items.not_matcher(&matcher)), vec!["b", "c"]);
+
+    assert!(items.try_not("li[").is_none());
+    assert_eq!(ids(&items.try_not("#c").unwrap()), vec!["a", "b", "d"]);
+    assert_eq!(items.not("p").length(), 4);
+
+    let disabled = doc.select(".disabled");
+    assert_eq!(ids(&items.not_selection(&disabled)), vec!["a", "c"]);
+    assert_eq!(items.not_selection(&doc.select("p")).length(), 4);
+    assert!(disabled.not_selection(&items).is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_has() {
+    let contents = r#"<div id="a" class="box"><p><img src="a.png"></p></div>
+        <div id="b" class="box"><p>Text</p></div>
+        <div id="c" class="box"><div id="d"><img src="d.png"></div></div>"#;
+    let doc = Document::from(contents);
+    let divs = doc.select("div");
+    let ids = |sel: &Selection| -> Vec<String> {
+        sel.iter()
+            .map(|s| s.attr_or("id", "").to_string())
+            .collect()
+    };
+
+    assert_eq!(ids(&divs.has("img")), vec!["a", "c", "d"]);
+    assert!(divs.has("img").same_nodes_as(&doc.select("div:has(img)")));
+
+    // the node itself is not tested
+    assert_eq!(ids(&divs.has(".box")), Vec::<String>::new());
+    assert_eq!(ids(&divs.has("div")), vec!["c"]);
+
+    let matcher = Matcher::new("p").unwrap();
+    assert_eq!(ids(&divs.has_matcher(&matcher)), vec!["a", "b"]);
+
+    assert!(divs.try_has("img[").is_none());
+    assert_eq!(ids(&divs.try_has("p > img").unwrap()), vec!["a"]);
+
+    assert!(doc.select("span").has("img").is_empty());
+}
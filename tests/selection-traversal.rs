@@ -3,7 +3,7 @@ mod data;
 use data::doc;
 use data::doc_wiki;
 use data::{ANCESTORS_CONTENTS, LIST_CONTENTS};
-use dom_query::Document;
+use dom_query::{Document, Matcher};
 
 use dom_query::Selection;
 #[cfg(target_arch = "wasm32")]
@@ -522,3 +522,358 @@ fn test_selection_get_node() {
     let third = sel.get(2);
     assert!(third.is_none());
 }
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_closest_with_attr() {
+    let contents = r#"<main data-section="main">
+        <section data-section="news">
+            <article><h2 id="news-title">News</h2><p id="news-text">Text</p></article>
+        </section>
+        <section data-section="sport" id="sport">
+            <h2 id="sport-title" data-section="sport-title">Sport</h2>
+        </section>
+        <aside><p id="aside-text">Aside</p></aside>
+    </main>
+    <footer><p id="footer-text">Footer</p></footer>"#;
+    let doc = Document::from(contents);
+
+    // nodes with the same nearest ancestor are collapsed into one
+    let sel = doc
+        .select("#news-title, #news-text")
+        .closest_with_attr("data-section");
+    assert_eq!(sel.length(), 1);
+    assert_eq!(sel.attr("data-section").unwrap().as_ref(), "news");
+
+    // the node itself is tested first
+    let sel = doc.select("#sport-title").closest_with_attr("data-section");
+    assert_eq!(sel.attr("data-section").unwrap().as_ref(), "sport-title");
+
+    let sel = doc.select("#aside-text").closest_with_attr("data-section");
+    assert_eq!(sel.attr("data-section").unwrap().as_ref(), "main");
+
+    // nodes without such ancestor are skipped
+    let sel = doc.select("#footer-text").closest_with_attr("data-section");
+    assert!(sel.is_empty());
+    let sel = doc
+        .select("#footer-text, #news-text")
+        .closest_with_attr("data-section");
+    assert_eq!(sel.length(), 1);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_text_nodes() {
+    let contents = r#"<div id="main">
+        <p>First <b>bold</b></p><p>Second</p>
+    </div><p id="outside">Outside</p>"#;
+    let doc = Document::from(contents);
+
+    // nested matches don't produce duplicates
+    let texts = doc.select("#main, #main p").text_nodes();
+    assert!(texts.nodes().iter().all(|node| node.is_text()));
+    let values: Vec<_> = texts.iter().map(|n| n.text().to_string()).collect();
+    assert_eq!(values.len(), 5);
+    assert_eq!(values[1..4], ["First ", "bold", "Second"]);
+
+    doc.select("#main p").text_nodes().set_text("x");
+    assert_eq!(
+        doc.select("#main").inner_html().as_ref(),
+        "\n        <p>x<b>x</b></p><p>x</p>\n    "
+    );
+    assert_eq!(doc.select("#outside").text().as_ref(), "Outside");
+
+    assert!(doc.select("#missing").text_nodes().is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_siblings() {
+    let contents = r#"<ul id="list">
+        <li id="one">One</li>
+        <!-- comment -->
+        <li id="two" class="active">Two</li>
+        text
+        <li id="three">Three</li>
+        <li id="four" class="active">Four</li>
+    </ul>"#;
+    let doc = Document::from(contents);
+
+    let siblings = doc.select("#two").siblings();
+    let ids: Vec<_> = siblings.iter().map(|s| s.attr_or("id", "")).collect();
+    assert_eq!(ids, vec!["one".into(), "three".into(), "four".into()]);
+
+    // siblings of several nodes are deduplicated, matched nodes may be siblings of each other
+    let siblings = doc.select("#one, #two").siblings();
+    let ids: Vec<_> = siblings.iter().map(|s| s.attr_or("id", "")).collect();
+    assert_eq!(
+        ids,
+        vec!["two".into(), "three".into(), "four".into(), "one".into()]
+    );
+
+    let matcher = Matcher::new(".active").unwrap();
+    let active = doc.select("#one").siblings_matcher(&matcher);
+    let ids: Vec<_> = active.iter().map(|s| s.attr_or("id", "")).collect();
+    assert_eq!(ids, vec!["two".into(), "four".into()]);
+
+    // the root node has no parent
+    assert!(Selection::from(doc.root()).siblings().is_empty());
+    assert!(doc.select("#missing").siblings().is_empty());
+
+    let sel = doc.select("#three");
+    let node = sel.nodes().first().unwrap();
+    let names: Vec<_> = node
+        .siblings()
+        .iter()
+        .map(|s| s.attr_or("id", ""))
+        .collect();
+    assert_eq!(names, vec!["one".into(), "two".into(), "four".into()]);
+    assert!(doc.root().siblings().is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_closest() {
+    let contents = r#"<div id="outer" class="box">
+        <div id="inner" class="box">
+            <p id="p1"><a id="a1" href="/1">One</a></p>
+            <p id="p2"><a id="a2" href="/2">Two</a></p>
+        </div>
+        <p id="p3"><a id="a3" href="/3">Three</a></p>
+    </div>"#;
+    let doc = Document::from(contents);
+
+    // the first match per node, deduplicated
+    let boxes = doc.select("a").closest(".box");
+    let ids: Vec<_> = boxes.iter().map(|s| s.attr_or("id", "")).collect();
+    assert_eq!(ids, vec!["inner".into(), "outer".into()]);
+
+    // a node that matches itself is returned unchanged
+    let ps = doc.select("p").closest("p");
+    assert!(ps.same_nodes_as(&doc.select("p")));
+
+    // non-element nodes are never returned
+    assert!(doc
+        .select("a")
+        .closest(":not(a, p, div, body, html)")
+        .is_empty());
+    assert!(Selection::from(doc.root()).closest("*").is_empty());
+
+    let matcher = Matcher::new("div").unwrap();
+    let divs = doc.select("#a3, #p3").closest_matcher(&matcher);
+    let ids: Vec<_> = divs.iter().map(|s| s.attr_or("id", "")).collect();
+    assert_eq!(ids, vec!["outer".into()]);
+
+    let sel = doc.select("#a1");
+    let node = sel.nodes().first().unwrap();
+    assert_eq!(
+        node.closest(&matcher).unwrap().attr("id").unwrap().as_ref(),
+        "inner"
+    );
+    assert!(node.closest(&Matcher::new("table").unwrap()).is_none());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_next_until_prev_until() {
+    let contents = r#"<table><tbody>
+        <tr id="h1" class="header"><th>A</th></tr>
+        <tr id="r1"><td>1</td></tr>
+        <!-- comment -->
+        <tr id="r2"><td>2</td></tr>
+        <tr id="h2" class="header"><th>B</th></tr>
+        <tr id="r3"><td>3</td></tr>
+    </tbody></table>"#;
+    let doc = Document::from(contents);
+    let ids = |sel: &Selection| -> Vec<String> {
+        sel.iter()
+            .map(|s| s.attr_or("id", "").to_string())
+            .collect()
+    };
+
+    let headers = doc.select("tr.header");
+    assert_eq!(ids(&headers.next_until(".header")), vec!["r1", "r2", "r3"]);
+    assert_eq!(
+        ids(&headers.first().next_until(".header")),
+        vec!["r1", "r2"]
+    );
+    assert_eq!(ids(&doc.select("#r1").next_until("#r3")), vec!["r2", "h2"]);
+
+    // preceding siblings, nearest first
+    assert_eq!(
+        ids(&doc.select("#r3").prev_until(".header")),
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        ids(&doc.select("#h2").prev_until(".header")),
+        vec!["r2", "r1"]
+    );
+
+    // overlapping results are deduplicated
+    let rows = doc.select("#r1, #r2").next_until("#r3");
+    assert_eq!(ids(&rows), vec!["r2", "h2"]);
+
+    // an empty or invalid selector collects all siblings
+    assert_eq!(ids(&doc.select("#r2").next_until("")), vec!["h2", "r3"]);
+    assert_eq!(ids(&doc.select("#r2").prev_until("[")), vec!["r1", "h1"]);
+
+    let matcher = Matcher::new("#r2").unwrap();
+    assert_eq!(
+        ids(&doc.select("#h1").next_until_matcher(&matcher)),
+        vec!["r1"]
+    );
+    assert_eq!(
+        ids(&doc.select("#r3").prev_until_matcher(&matcher)),
+        vec!["h2"]
+    );
+
+    assert!(doc.select("#missing").next_until("tr").is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_eq_slice() {
+    let doc = Document::from("<ul><li>0</li><li>1</li><li>2</li><li>3</li><li>4</li></ul>");
+    let items = doc.select("li");
+
+    assert_eq!(items.eq(0).text().as_ref(), "0");
+    assert_eq!(items.eq(3).text().as_ref(), "3");
+    assert!(items.eq(5).is_empty());
+    assert!(doc.select("p").eq(0).is_empty());
+
+    assert_eq!(items.slice(0..2).text().as_ref(), "01");
+    assert_eq!(items.slice(1..=3).text().as_ref(), "123");
+    assert_eq!(items.slice(3..).text().as_ref(), "34");
+    assert_eq!(items.slice(..).length(), 5);
+    assert_eq!(items.slice(0..usize::MAX).length(), 5);
+    assert_eq!(items.slice(2..=usize::MAX).text().as_ref(), "234");
+    assert!(items.slice(3..3).is_empty());
+    assert!(items.slice(10..).is_empty());
+    assert!(doc.select("p").slice(0..10).is_empty());
+
+    // pagination
+    let pages: Vec<_> = (0..3)
+        .map(|page| items.slice(page * 2..page * 2 + 2).text().to_string())
+        .collect();
+    assert_eq!(pages, vec!["01", "23", "4"]);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_map_each() {
+    let contents = r#"<div>
+        <a href="/one">One</a>
+        <a href="/two">Two</a>
+        <a>Three</a>
+    </div>"#;
+    let doc = Document::from(contents);
+    let links = doc.select("a");
+
+    let hrefs = links.map(|_, node| node.attr("href"));
+    assert_eq!(hrefs, vec![Some("/one".into()), Some("/two".into()), None]);
+
+    let indexed: Vec<(usize, String)> = links.map(|i, node| (i, node.text().to_string()));
+    assert_eq!(
+        indexed,
+        vec![
+            (0, "One".to_string()),
+            (1, "Two".to_string()),
+            (2, "Three".to_string())
+        ]
+    );
+
+    let mut visited = vec![];
+    let chained = links
+        .each(|i, node| {
+            visited.push(i);
+            node.set_attr("data-pos", &i.to_string());
+        })
+        .filter("[href]");
+    assert_eq!(visited, vec![0, 1, 2]);
+    assert_eq!(chained.length(), 2);
+    assert_eq!(doc.select(r#"a[data-pos="2"]"#).text().as_ref(), "Three");
+
+    assert!(doc.select("p").map(|i, _| i).is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_contents() {
+    let contents =
+        r#"<div id="main">Intro <b>bold</b><!-- note --><p id="p">Para <i>text</i></p></div>"#;
+    let doc = Document::from(contents);
+
+    let main = doc.select("#main");
+    let children = main.contents();
+    assert_eq!(children.length(), 4);
+    let nodes = children.nodes();
+    assert!(nodes[0].is_text());
+    assert_eq!(nodes[0].text().as_ref(), "Intro ");
+    assert!(nodes[1].is_element());
+    assert!(nodes[2].is_comment());
+    assert_eq!(nodes[3].attr("id").unwrap().as_ref(), "p");
+    assert_eq!(main.children().length(), 2);
+
+    // nested selected nodes don't produce duplicates
+    let all = doc.select("#main, #p").contents();
+    assert_eq!(all.length(), 6);
+    let all_again = doc.select("#main, #p, #main").contents();
+    assert!(all.same_nodes_as(&all_again));
+
+    // element methods don't affect text and comment nodes
+    children.add_class("touched");
+    assert_eq!(doc.select(".touched").length(), 2);
+    assert!(doc.select("#missing").contents().is_empty());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn test_selection_parents() {
+    let contents = r#"<article id="art">
+        <section id="sec" class="part">
+            <div id="outer" class="part">
+                <p id="p1">One</p>
+                <p id="p2">Two</p>
+            </div>
+        </section>
+    </article>"#;
+    let doc = Document::from(contents);
+    let names = |sel: &Selection| -> Vec<String> {
+        sel.iter()
+            .map(|s| {
+                s.attr("id")
+                    .map(|id| id.to_string())
+                    .unwrap_or_else(|| s.nodes()[0].node_name().unwrap().to_string())
+            })
+            .collect()
+    };
+
+    // child-to-ancestor order, deduplicated across input nodes
+    let ps = doc.select("p");
+    assert_eq!(
+        names(&ps.parents(None)),
+        vec!["outer", "sec", "art", "body", "html"]
+    );
+    assert_eq!(names(&ps.parents(Some(".part"))), vec!["outer", "sec"]);
+    assert!(ps.parents(Some("table")).is_empty());
+
+    assert_eq!(names(&ps.parents_until("article")), vec!["outer", "sec"]);
+    assert_eq!(names(&ps.parents_until(".part")), Vec::<String>::new());
+    assert_eq!(
+        names(&doc.select("#outer").parents_until("body")),
+        vec!["sec", "art"]
+    );
+
+    // an empty or invalid selector collects all ancestors
+    assert_eq!(ps.parents_until("").length(), 5);
+    assert_eq!(ps.parents_until("[").length(), 5);
+
+    let matcher = Matcher::new("#art").unwrap();
+    assert_eq!(
+        names(&doc.select("#p1").parents_until_matcher(&matcher)),
+        vec!["outer", "sec"]
+    );
+
+    assert!(Selection::from(doc.root()).parents(None).is_empty());
+    assert!(doc.select("#missing").parents_until("body").is_empty());
+}